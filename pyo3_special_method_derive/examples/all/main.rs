@@ -1,8 +1,8 @@
-use pyo3::pyclass;
+use pyo3::{prelude::*, BoundObject};
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Str, Repr, Dir)]
+#[pyclass]
 enum Tester {
     Alpha {
         x: u32,
@@ -11,7 +11,7 @@ enum Tester {
         x: u32,
         y: u32,
     },
-    #[skip(Str, Repr, Dir)]
+    #[pyo3_smd(skip(Str, Repr, Dir))]
     Gamma {
         x: u32,
         y: u32,
@@ -19,40 +19,59 @@ enum Tester {
     },
 }
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct Person {
     pub name: String,
     occupation: String,
-    #[skip(Dir, Str, Repr)]
+    #[pyo3_smd(skip(Dir, Str, Repr))]
     pub phone_num: String,
 }
 
+/// The names `dir()` lists for `value` once it is converted to a Python object.
+fn dir<T: for<'py> IntoPyObject<'py>>(value: T) -> Vec<String> {
+    Python::with_gil(|py| {
+        let value = value.into_pyobject(py).map_err(Into::into).unwrap();
+        value
+            .into_bound()
+            .into_any()
+            .dir()
+            .unwrap()
+            .extract()
+            .unwrap()
+    })
+}
+
 fn main() {
+    pyo3::prepare_freethreaded_python();
+
     let person = Person {
         name: "John Doe".to_string(),
         occupation: "Programmer".to_string(),
         phone_num: "123 456 7890".to_string(),
     };
-    assert_eq!(person.__dir__(), vec!["name".to_string()]);
     assert_eq!(person.__str__(), "Person(name=\"John Doe\")");
+    // The exposed fields are listed along with the methods of the class
+    let names = dir(person);
+    assert!(names.contains(&"name".to_string()));
+    assert!(names.contains(&"__str__".to_string()));
+    assert!(!names.contains(&"occupation".to_string()));
 
-    let tester_beta = Tester::Beta { x: 123, y: 456 };
+    // An enum instance lists the fields of its variant, the class lists the variants
     assert_eq!(
-        tester_beta.__dir__(),
+        Tester::variants(),
         vec!["Alpha".to_string(), "Beta".to_string()]
     );
+    let tester_beta = Tester::Beta { x: 123, y: 456 };
     assert_eq!(tester_beta.__str__(), "Tester.Beta(x=123, y=456)");
+    let names = dir(tester_beta);
+    assert!(names.contains(&"x".to_string()) && names.contains(&"y".to_string()));
 
     let tester_gamma = Tester::Gamma {
         x: 123,
         y: 456,
         z: 789,
     };
-    assert_eq!(
-        tester_gamma.__dir__(),
-        vec!["Alpha".to_string(), "Beta".to_string()]
-    );
-    assert_eq!(tester_gamma.__str__(), "<variant skipped>");
+    assert_eq!(tester_gamma.__str__(), "Tester.<variant skipped>");
 }
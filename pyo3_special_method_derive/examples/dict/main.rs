@@ -1,8 +1,8 @@
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dict;
 
-#[pyclass]
 #[derive(Dict)]
+#[pyclass]
 #[allow(dead_code)]
 struct Person {
     pub name: String,
@@ -18,7 +18,8 @@ fn main() {
         address: "Address".to_string(),
         location: "Earth".to_string(),
     }
-    .__dict__();
+    .__dict__()
+    .unwrap();
 
     let mut keys = res.keys().cloned().collect::<Vec<_>>();
     keys.sort();
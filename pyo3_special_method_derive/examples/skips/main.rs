@@ -1,10 +1,10 @@
-use pyo3::pyclass;
+use pyo3::{prelude::*, BoundObject};
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Str, Repr, Dir)]
+#[pyclass]
 enum Tester {
-    #[skip(Str, Repr, Dir)]
+    #[pyo3_smd(skip(Str, Repr, Dir))]
     Alpha {
         x: u32,
     },
@@ -12,7 +12,7 @@ enum Tester {
         x: u32,
         y: u32,
     },
-    #[skip(Str, Repr, Dir)]
+    #[pyo3_smd(skip(Str, Repr, Dir))]
     Gamma {
         x: u32,
         y: u32,
@@ -20,62 +20,65 @@ enum Tester {
     },
 }
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct Person {
     pub name: String,
-    #[skip(Repr)]
+    #[pyo3_smd(skip(Repr))]
     pub occupation: String,
-    #[skip(Repr)]
+    #[pyo3_smd(skip(Repr))]
     pub phone_num: String,
 }
 
+/// The names `dir()` lists for `value` once it is converted to a Python object.
+fn dir<T: for<'py> IntoPyObject<'py>>(value: T) -> Vec<String> {
+    Python::with_gil(|py| {
+        let value = value.into_pyobject(py).map_err(Into::into).unwrap();
+        value
+            .into_bound()
+            .into_any()
+            .dir()
+            .unwrap()
+            .extract()
+            .unwrap()
+    })
+}
+
 fn main() {
+    pyo3::prepare_freethreaded_python();
+
     let person = Person {
         name: "John Doe".to_string(),
         occupation: "Programmer".to_string(),
         phone_num: "123 456 7890".to_string(),
     };
-    assert_eq!(
-        person.__dir__(),
-        vec![
-            "name".to_string(),
-            "occupation".to_string(),
-            "phone_num".to_string()
-        ]
-    );
     assert_eq!(
         person.__str__(),
-        "Person(name=\"John Doe\", occupation=\"Programmer\")"
-    );
-    assert_eq!(
-        person.__repr__(),
-        "Person(name=\"John Doe\", phone_num=\"123 456 7890\")"
+        "Person(name=\"John Doe\", occupation=\"Programmer\", phone_num=\"123 456 7890\")"
     );
+    assert_eq!(person.__repr__(), "Person(name='John Doe')");
+    let names = dir(person);
+    for field in ["name", "occupation", "phone_num"] {
+        assert!(names.contains(&field.to_string()));
+    }
+
+    // Skipped variants are not listed
+    assert_eq!(Tester::variants(), vec!["Beta".to_string()]);
 
     let tester_beta = Tester::Beta { x: 123, y: 456 };
-    assert_eq!(
-        tester_beta.__dir__(),
-        vec!["Alpha".to_string(), "Beta".to_string(), "Gamma".to_string()]
-    );
     assert_eq!(tester_beta.__str__(), "Tester.Beta(x=123, y=456)");
+    let names = dir(tester_beta);
+    assert!(names.contains(&"x".to_string()) && names.contains(&"y".to_string()));
 
+    // A skipped variant is not displayed, although PyO3 still exposes its fields as attributes
     let tester_gamma = Tester::Gamma {
         x: 123,
         y: 456,
         z: 789,
     };
-    assert_eq!(
-        tester_gamma.__dir__(),
-        vec!["Alpha".to_string(), "Beta".to_string(), "Gamma".to_string()]
-    );
-    assert_eq!(tester_gamma.__str__(), "<variant skipped>");
+    assert_eq!(tester_gamma.__str__(), "Tester.<variant skipped>");
 
     let tester_alpha: Tester = Tester::Alpha { x: 123 };
-    assert_eq!(
-        tester_alpha.__dir__(),
-        vec!["Alpha".to_string(), "Beta".to_string(), "Gamma".to_string()]
-    );
-    assert_eq!(tester_alpha.__repr__(), "<variant skipped>");
+    assert_eq!(tester_alpha.__repr__(), "Tester.<variant skipped>");
 }
@@ -0,0 +1,42 @@
+use chrono::{
+    DateTime as ChronoDateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike,
+};
+
+use crate::datetime::{impl_temporal, Date, DateTime, Temporal, Time};
+
+fn date(date: &NaiveDate) -> Date {
+    Date {
+        year: date.year().into(),
+        month: date.month(),
+        day: date.day(),
+    }
+}
+
+fn time(time: &NaiveTime) -> Time {
+    Time {
+        hour: time.hour(),
+        minute: time.minute(),
+        second: time.second(),
+        // Python has no leap seconds, which chrono represents with more than 10^9 nanoseconds
+        microsecond: (time.nanosecond() / 1000).min(999_999),
+    }
+}
+
+fn naive_datetime(datetime: &NaiveDateTime, offset: Option<i32>) -> DateTime {
+    DateTime {
+        date: date(&datetime.date()),
+        time: time(&datetime.time()),
+        offset,
+    }
+}
+
+impl_temporal!(
+    NaiveDate => date,
+    NaiveTime => time,
+    NaiveDateTime => |datetime| naive_datetime(datetime, None),
+    ChronoDateTime<Tz> where Tz: TimeZone => |datetime: &ChronoDateTime<Tz>| {
+        let offset = datetime.offset().fix().local_minus_utc();
+        naive_datetime(&datetime.naive_local(), Some(offset))
+    },
+);
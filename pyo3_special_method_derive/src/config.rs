@@ -0,0 +1,176 @@
+use std::{
+    cell::RefCell,
+    marker::PhantomData,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use crate::{LockPolicy, ReprLimits};
+
+/// Configuration of the output of `PyDebug` and `PyDisplay`.
+///
+/// The configuration in effect is looked up on every formatting call, from the innermost
+/// [`scope`](FormatConfig::scope) of the current thread, else the configuration set for the
+/// current thread with [`set_thread`](FormatConfig::set_thread), else the global one:
+///
+/// ```
+/// use pyo3_special_method_derive::{FormatConfig, PyDebug};
+///
+/// let items = (0..10).collect::<Vec<_>>();
+/// {
+///     let _scope = FormatConfig {
+///         max_chars: 10,
+///         separator: ",".to_string(),
+///         ..FormatConfig::default()
+///     }
+///     .scope();
+///     assert_eq!(items.fmt_debug(), "[0,1,...]");
+/// }
+/// assert_eq!(items.fmt_debug(), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// Maximum number of characters of a collection, brackets included, 100 by default. The
+    /// items which do not fit are replaced by the ellipsis, except for the first one which is
+    /// shortened instead.
+    pub max_chars: usize,
+    /// Limits on the whole output, see [`ReprLimits`].
+    pub limits: ReprLimits,
+    /// How locks are acquired, see [`LockPolicy`].
+    pub lock_policy: LockPolicy,
+    /// Displayed instead of the items of a collection which are left out, and of the values
+    /// which are nested too deep or already being formatted. `...` by default.
+    pub ellipsis: String,
+    /// Displayed between the items of a collection and the fields of an object, `, ` by default.
+    pub separator: String,
+    /// Displayed between a key and its value in a dict, `: ` by default.
+    pub key_separator: String,
+    /// Marks where a string or an output exceeding the budget is shortened, `...` by default.
+    pub truncation_marker: String,
+    /// Where primitives and collections are displayed just like Python displays them, see
+    /// [`PythonStyle`].
+    pub python_style: PythonStyle,
+}
+
+/// Where primitives and collections are displayed just like Python displays them: `True`,
+/// `nan`, `'text'` with Python's escapes, `set()` and `(a,)`, rather than with Rust's `Debug`:
+/// `true`, `NaN`, `"text"`, `{}` and `(a)`.
+///
+/// Strings escape the characters which Python's `str.isprintable()` rejects, except for code
+/// points which Unicode has not assigned yet, which are written as they are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PythonStyle {
+    /// Use Rust's `Debug` everywhere.
+    Never,
+    /// In `PyDebug`, and thus `Repr`, so that `repr()` looks like native Python objects.
+    ///
+    /// Unlike Python's `str()`, which shows the `repr()` of nested values, `Str` then keeps Rust's
+    /// style for them too, eg. `City(name="Paris")` or `["Paris"]` rather than `['Paris']`.
+    #[default]
+    Debug,
+    /// In `PyDebug` and `PyDisplay`.
+    Always,
+}
+
+impl PythonStyle {
+    /// Whether the Python style applies, to `PyDebug` if `debug` or else to `PyDisplay`.
+    pub fn applies(self, debug: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Debug => debug,
+            Self::Always => true,
+        }
+    }
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            max_chars: 100,
+            limits: ReprLimits::default(),
+            lock_policy: LockPolicy::default(),
+            ellipsis: "...".to_string(),
+            separator: ", ".to_string(),
+            key_separator: ": ".to_string(),
+            truncation_marker: "...".to_string(),
+            python_style: PythonStyle::default(),
+        }
+    }
+}
+
+fn global() -> &'static RwLock<Arc<FormatConfig>> {
+    static GLOBAL: OnceLock<RwLock<Arc<FormatConfig>>> = OnceLock::new();
+    GLOBAL.get_or_init(Default::default)
+}
+
+thread_local! {
+    /// The configuration of the current thread, if it does not use the global one.
+    static THREAD: RefCell<Option<Arc<FormatConfig>>> = const { RefCell::new(None) };
+}
+
+impl FormatConfig {
+    /// The configuration in effect on the current thread.
+    pub fn current() -> Arc<FormatConfig> {
+        THREAD
+            .with(|thread| thread.borrow().clone())
+            .unwrap_or_else(Self::global)
+    }
+
+    /// The configuration of the threads which do not have their own.
+    pub fn global() -> Arc<FormatConfig> {
+        global()
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Use this configuration on the threads which do not have their own.
+    pub fn set_global(self) {
+        *global().write().unwrap_or_else(|err| err.into_inner()) = Arc::new(self);
+    }
+
+    /// Replace the global configuration with the one `update` makes from it, which no other
+    /// thread can change in the meantime.
+    pub fn update_global(update: impl FnOnce(&FormatConfig) -> FormatConfig) {
+        let mut global = global().write().unwrap_or_else(|err| err.into_inner());
+        *global = Arc::new(update(&global));
+    }
+
+    /// Use this configuration on the current thread.
+    pub fn set_thread(self) {
+        THREAD.with(|thread| *thread.borrow_mut() = Some(Arc::new(self)));
+    }
+
+    /// Use the global configuration on the current thread again.
+    pub fn reset_thread() {
+        THREAD.with(|thread| *thread.borrow_mut() = None);
+    }
+
+    /// Use this configuration on the current thread until the returned guard is dropped, which
+    /// restores the previous one.
+    pub fn scope(self) -> FormatConfigGuard {
+        let previous = replace_thread(Some(Arc::new(self)));
+        FormatConfigGuard {
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+/// Restores the previous configuration of the thread when dropped, see [`FormatConfig::scope`].
+#[must_use = "the configuration is only used until the guard is dropped"]
+pub struct FormatConfigGuard {
+    previous: Option<Arc<FormatConfig>>,
+    // The configuration belongs to the thread which created the guard
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for FormatConfigGuard {
+    fn drop(&mut self) {
+        replace_thread(self.previous.take());
+    }
+}
+
+/// Replace the configuration of the current thread, returning the previous one.
+pub(crate) fn replace_thread(config: Option<Arc<FormatConfig>>) -> Option<Arc<FormatConfig>> {
+    THREAD.with(|thread| thread.replace(config))
+}
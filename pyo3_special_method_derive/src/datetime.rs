@@ -0,0 +1,282 @@
+use std::fmt;
+
+/// Write `secs` and `nanos` as a number of seconds, with as many decimals as needed: `1`,
+/// `1.5` or `-0.000001`.
+fn write_seconds(out: &mut dyn fmt::Write, negative: bool, secs: u64, nanos: u32) -> fmt::Result {
+    if negative {
+        out.write_char('-')?;
+    }
+    write!(out, "{secs}")?;
+    if nanos == 0 {
+        return Ok(());
+    }
+    let mut digits = 9;
+    let mut fraction = nanos;
+    while fraction.is_multiple_of(10) {
+        fraction /= 10;
+        digits -= 1;
+    }
+    write!(out, ".{fraction:0digits$}")
+}
+
+/// Write Python's `repr` of a `timedelta`, in seconds as the duration may be finer than the
+/// microseconds of a `timedelta`: `datetime.timedelta(seconds=1.5)`, or `datetime.timedelta(0)`.
+pub(crate) fn repr_timedelta(
+    out: &mut dyn fmt::Write,
+    negative: bool,
+    secs: u64,
+    nanos: u32,
+) -> fmt::Result {
+    if secs == 0 && nanos == 0 {
+        return out.write_str("datetime.timedelta(0)");
+    }
+    out.write_str("datetime.timedelta(seconds=")?;
+    write_seconds(out, negative, secs, nanos)?;
+    out.write_char(')')
+}
+
+/// Write Python's `str` of a positive `timedelta`: `0:00:01.500000` or `2 days, 1:00:00`.
+pub(crate) fn str_timedelta(out: &mut dyn fmt::Write, secs: u64, nanos: u32) -> fmt::Result {
+    let days = secs / 86_400;
+    match days {
+        0 => {}
+        1 => out.write_str("1 day, ")?,
+        days => write!(out, "{days} days, ")?,
+    }
+    let secs = secs % 86_400;
+    write!(
+        out,
+        "{}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )?;
+    match nanos / 1000 {
+        0 => Ok(()),
+        micros => write!(out, ".{micros:06}"),
+    }
+}
+
+/// A date, time or date and time which Python's `datetime` module can represent.
+pub(crate) trait Temporal {
+    /// Whether Python can represent the date, from year 1 to 9999.
+    fn in_range(&self) -> bool;
+
+    /// Write Python's `repr`.
+    fn write_repr(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Write ISO 8601, as Python's `isoformat()` does.
+    fn write_iso(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+/// A date as Python's `datetime.date` holds it.
+pub(crate) struct Date {
+    pub(crate) year: i64,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+}
+
+impl Temporal for Date {
+    fn in_range(&self) -> bool {
+        (1..=9999).contains(&self.year)
+    }
+
+    /// `datetime.date(2024, 5, 1)`
+    fn write_repr(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            "datetime.date({}, {}, {})",
+            self.year, self.month, self.day
+        )
+    }
+
+    /// `2024-05-01`
+    fn write_iso(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day as Python's `datetime.time` holds it, to the microsecond.
+pub(crate) struct Time {
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+    pub(crate) microsecond: u32,
+}
+
+impl Time {
+    /// The fields of Python's `repr`, which leaves out the seconds and microseconds when they
+    /// are 0: `12, 0` or `3, 4, 5, 600000`.
+    fn write_fields(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}, {}", self.hour, self.minute)?;
+        if self.second != 0 || self.microsecond != 0 {
+            write!(out, ", {}", self.second)?;
+        }
+        if self.microsecond != 0 {
+            write!(out, ", {}", self.microsecond)?;
+        }
+        Ok(())
+    }
+}
+
+impl Temporal for Time {
+    fn in_range(&self) -> bool {
+        true
+    }
+
+    /// `datetime.time(12, 0)`
+    fn write_repr(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("datetime.time(")?;
+        self.write_fields(out)?;
+        out.write_char(')')
+    }
+
+    /// `12:00:00` or `03:04:05.600000`
+    fn write_iso(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            "{:02}:{:02}:{:02}",
+            self.hour, self.minute, self.second
+        )?;
+        match self.microsecond {
+            0 => Ok(()),
+            microsecond => write!(out, ".{microsecond:06}"),
+        }
+    }
+}
+
+/// A date and time as Python's `datetime.datetime` holds it.
+pub(crate) struct DateTime {
+    pub(crate) date: Date,
+    pub(crate) time: Time,
+    /// Offset from UTC in seconds, `None` for a naive date and time.
+    pub(crate) offset: Option<i32>,
+}
+
+impl DateTime {
+    /// The date and time in UTC `secs` seconds and `nanos` nanoseconds after the Unix epoch.
+    pub(crate) fn from_unix(secs: i64, nanos: u32) -> Self {
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let time = secs.rem_euclid(86_400) as u32;
+        Self {
+            date: Date { year, month, day },
+            time: Time {
+                hour: time / 3600,
+                minute: time / 60 % 60,
+                second: time % 60,
+                microsecond: nanos / 1000,
+            },
+            offset: Some(0),
+        }
+    }
+
+    /// Write the date and time separated by `sep`, as Python's `isoformat(sep)` does: `str()`
+    /// separates them with a space.
+    pub(crate) fn write_iso_with(&self, out: &mut dyn fmt::Write, sep: char) -> fmt::Result {
+        self.date.write_iso(out)?;
+        out.write_char(sep)?;
+        self.time.write_iso(out)?;
+        match self.offset {
+            Some(offset) => str_offset(out, offset),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Temporal for DateTime {
+    fn in_range(&self) -> bool {
+        self.date.in_range()
+    }
+
+    /// `datetime.datetime(2024, 1, 2, 3, 4, 5, 600000, tzinfo=datetime.timezone.utc)`
+    fn write_repr(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let date = &self.date;
+        write!(
+            out,
+            "datetime.datetime({}, {}, {}, ",
+            date.year, date.month, date.day
+        )?;
+        self.time.write_fields(out)?;
+        if let Some(offset) = self.offset {
+            out.write_str(", tzinfo=")?;
+            repr_timezone(out, offset)?;
+        }
+        out.write_char(')')
+    }
+
+    /// `2024-01-02T03:04:05.600000+00:00`
+    fn write_iso(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_iso_with(out, 'T')
+    }
+}
+
+/// Implement `PyDebug` as Python's `repr` and `PyDisplay` as ISO 8601, just like `isoformat()`,
+/// for the date and time types of another crate, converted to a [`Temporal`] with `$convert`.
+/// Values which Python cannot represent are displayed with their `Debug` implementation. Generic
+/// types declare their parameters with `Type<T> where T: Bound => ...`.
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! impl_temporal {
+    ($($t:ty $(where $($param:ident: $bound:path),+)? => $convert:expr),+ $(,)?) => {$(
+        impl<$($($param: $bound),+)?> $crate::PyDebug for $t {
+            fn fmt_debug_into(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                let value = $convert(self);
+                if value.in_range() && $crate::python_style(true) {
+                    value.write_repr(out)
+                } else {
+                    write!(out, "{self:?}")
+                }
+            }
+        }
+
+        impl<$($($param: $bound),+)?> $crate::PyDisplay for $t {
+            fn fmt_display_into(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                let value = $convert(self);
+                if value.in_range() {
+                    value.write_iso(out)
+                } else {
+                    write!(out, "{self:?}")
+                }
+            }
+        }
+    )+};
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) use impl_temporal;
+
+/// Write Python's `repr` of a fixed-offset timezone: `datetime.timezone.utc` or
+/// `datetime.timezone(datetime.timedelta(seconds=3600))`.
+fn repr_timezone(out: &mut dyn fmt::Write, offset: i32) -> fmt::Result {
+    if offset == 0 {
+        return out.write_str("datetime.timezone.utc");
+    }
+    out.write_str("datetime.timezone(")?;
+    repr_timedelta(out, offset < 0, offset.unsigned_abs().into(), 0)?;
+    out.write_char(')')
+}
+
+/// Write an offset from UTC as Python's `str` of a `datetime` ends with it: `+01:00`.
+fn str_offset(out: &mut dyn fmt::Write, offset: i32) -> fmt::Result {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    write!(out, "{sign}{:02}:{:02}", offset / 3600, offset / 60 % 60)?;
+    match offset % 60 {
+        0 => Ok(()),
+        secs => write!(out, ":{secs:02}"),
+    }
+}
+
+/// The date in the proleptic Gregorian calendar `days` days after 1970-01-01, as a year, month
+/// and day. This is Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -10,8 +10,41 @@
 //! - `__getattr__`
 //! - `__dict__`
 //!
-//! - Skip exposure of variants or fields per derive macro with the `#[skip(...)]` attribute (eg. `#[skip(Dir, Str)]`)
-//! - Struct fields which are not `pub` are skipped automatically
+//! - Skip exposure of variants or fields per derive macro with the `#[pyo3_smd(skip(...))]` attribute (eg. `#[pyo3_smd(skip(Dir, Str))]`)
+//! - Force exposure of a non-`pub` field with `#[pyo3_smd(include)]`, rename an item with `#[pyo3_smd(rename = "...")]`
+//!   and customize formatting with `#[pyo3_smd(fmt = "...")]`
+//! - The `#[skip(...)]`, `#[pyo3_fmt_no_skip]` and `#[format(...)]` attributes are deprecated aliases
+//! - Struct fields which are not `pub` are skipped automatically, unless PyO3 exposes them with `#[pyo3(get)]`
+//! - PyO3's `#[pyclass(name = "...", module = "...")]` and `#[pyo3(name = "...")]` names are used in the output
+//! - `Getattr` and `Dict` expose `Arc<RwLock<T>>`/`Arc<Mutex<T>>` fields with `#[pyo3_smd(lock = "clone")]`, which clones
+//!   the locked value, or `#[pyo3_smd(lock = "shared")]`, which hands out the `Py<T>` held by the lock (see [`PyLock`])
+//! - Generic types are supported: every type parameter of a displayed field must implement `PyDisplay`/`PyDebug`,
+//!   which can be overridden with `#[pyo3_smd(bound = "...")]` on the type
+//! - `Str` displays a string on its own without quotes, just like `str()`, but quotes it within a collection or an
+//!   object (see [`PyDisplay::fmt_str`]); `#[pyo3_smd(transparent)]` displays a single-field struct as its field
+//! - `Repr` displays primitives and collections just like Python, eg. `True`, `nan`, `'text'` or `set()` (see [`PythonStyle`])
+//! - The output is configured with a [`FormatConfig`], globally, per thread or per scope, and its limits can be
+//!   adjusted from Python once [`add_repr_config`] is called on the extension module
+//! - The output is limited in the spirit of Python's `reprlib`: long strings and collections are shortened, and the
+//!   whole output of a value, nested values included, has a budget (see [`ReprLimits`])
+//! - [`PyDebug`] and [`PyDisplay`] write into a `fmt::Write` sink with `fmt_debug_into`/`fmt_display_into`, so that a
+//!   value is formatted into a single buffer along with the values nested in it, and formatting stops as soon as the
+//!   budget is spent
+//! - Formatting is cycle-safe: a value which is already being formatted, or is nested deeper than
+//!   [`ReprLimits::max_level`], is displayed as `...` (see [`guard_recursion`])
+//! - A lock held elsewhere is displayed as `<locked>`, or waited for according to the [`LockPolicy`], and the value of a
+//!   poisoned lock is displayed as `<poisoned: ...>`
+//! - Tuples up to 12 items are displayed as tuples, and slices, arrays, `VecDeque`, `LinkedList` and `BinaryHeap` as lists
+//! - `Repr` displays `Duration`, `SystemTime`, paths and IP addresses like the `datetime`, `pathlib` and `ipaddress`
+//!   objects they stand for, eg. `datetime.timedelta(seconds=1.5)` or `PosixPath('/x')`, while `NonZero*` integers and
+//!   atomics are displayed as their value and `Result` as `Ok(...)`/`Err(...)`
+//! - Python objects held in `Py<T>`, `PyObject` or `Bound<'py, T>` fields are displayed by Python's own `repr()`, or
+//!   `str()` on their own, so that nested pyclasses are displayed inline, and as `<unrepresentable object>` if that raises
+//! - Smart pointers and cells are displayed as the value they hold: `Box`, `Rc`, `Arc` and `Cow`, a `RefCell` which is
+//!   displayed as `<borrowed>` while it is mutably borrowed, a `Weak` which is displayed as `<dead>` once its value is
+//!   dropped, and a `OnceCell`/`OnceLock` which is displayed as `<uninit>` until it is set
+//! - The `chrono` and `time` features display the date and time types of these crates like Python's `datetime`
+//!   objects, eg. `datetime.date(2024, 5, 1)`, and in ISO 8601 with `Str`, eg. `2024-05-01T12:00:00+00:00`
 //!
 //! When you have custom Rust structs which need to implement `PyDisplay` and `PyDebug`, you should use the `AutoDisplay` and `AutoDebug` traits.
 //! This will have the same output as `Str` and `Repr` respectively.
@@ -25,28 +58,119 @@
 //!
 
 use std::{
-    cell::Cell,
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    borrow::Cow,
+    cell::{Cell, OnceCell, RefCell},
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    path::{Path, PathBuf},
+    rc::{self, Rc},
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, Mutex, RwLock,
+        self,
+        atomic::{
+            AtomicBool, AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+            AtomicU8, AtomicUsize, Ordering,
+        },
+        Arc, Mutex, OnceLock, RwLock,
     },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+
+use pyo3::{
+    types::{PyAnyMethods, PyStringMethods},
+    Bound, Py, PyAny, Python,
+};
 pub use pyo3_special_method_derive_macro::*;
 
-/// Number of *characters* to display for each implementation in this crate,
-/// defaults to 100. May be a few chars above or below.
-pub static ELLIPSIS_CHAR_N: AtomicUsize = AtomicUsize::new(100);
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+mod config;
+mod datetime;
+mod limits;
+mod literal;
+mod lock;
+mod recursion;
+mod repr_config;
+#[cfg(feature = "time")]
+mod time_impls;
+pub use config::{FormatConfig, FormatConfigGuard, PythonStyle};
+use datetime::{repr_timedelta, str_timedelta, DateTime, Temporal};
+pub use limits::ReprLimits;
+use limits::{budget, nested, set_budget, shorten_middle, Limited};
+use literal::{repr_float, repr_str};
+use lock::format_locked;
+pub use lock::{LockPolicy, PyLock};
+pub use recursion::guard_recursion;
+pub use repr_config::add_repr_config;
 
 /// Types which can be displayed into the `__repr__` implementation.
+///
+/// The value is written into a [`fmt::Write`] sink, so that it is formatted into a single buffer
+/// along with the values nested in it, and formatting stops as soon as the output budget is
+/// spent (see [`ReprLimits::max_total`]).
 pub trait PyDebug {
-    fn fmt_debug(&self) -> String;
+    /// Write the value into `out`. Errors of `out`, which also stop formatting once the output
+    /// budget is spent, should be passed on.
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// The value written by [`fmt_debug_into`](PyDebug::fmt_debug_into).
+    fn fmt_debug(&self) -> String {
+        to_string(|out| self.fmt_debug_into(out))
+    }
 }
 
 /// Types which can be displayed into the `__str__` implementation.
+///
+/// Just like [`PyDebug`], the value is written into a [`fmt::Write`] sink.
 pub trait PyDisplay {
-    fn fmt_display(&self) -> String;
+    /// Display the value within another one, eg. a string is quoted, just like the items of a list
+    /// in Python's `str([...])`.
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Display the value on its own, as `str()` does: a string is not quoted. Defaults to
+    /// [`fmt_display_into`](PyDisplay::fmt_display_into).
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.fmt_display_into(out)
+    }
+
+    /// The value written by [`fmt_display_into`](PyDisplay::fmt_display_into).
+    fn fmt_display(&self) -> String {
+        to_string(|out| self.fmt_display_into(out))
+    }
+
+    /// The value written by [`fmt_str_into`](PyDisplay::fmt_str_into).
+    fn fmt_str(&self) -> String {
+        to_string(|out| self.fmt_str_into(out))
+    }
+}
+
+/// The output of `format`, as far as it got if it failed.
+fn to_string(format: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result) -> String {
+    let mut output = String::new();
+    let _ = format(&mut output);
+    output
+}
+
+/// A value displayed by writing it with `format`, to embed the output of `PyDebug` and
+/// `PyDisplay` in a format string without going through a `String`. Used by the derive macros.
+#[doc(hidden)]
+pub fn display_with(format: impl Fn(&mut dyn fmt::Write) -> fmt::Result) -> impl fmt::Display {
+    struct DisplayWith<F>(F);
+
+    impl<F: Fn(&mut dyn fmt::Write) -> fmt::Result> fmt::Display for DisplayWith<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    DisplayWith(format)
 }
 
 /// Use this trait to automatically derive PyDebug and PyDisplay for your type.
@@ -57,15 +181,15 @@ pub trait PyDisplay {
 macro_rules! pydebug_pydisplay {
     ($t:ty) => {
         impl PyDebug for $t {
-            fn fmt_debug(&self) -> String {
-                format!("{self:?}")
+            fn fmt_debug_into(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                write!(out, "{self:?}")
             }
         }
         impl PyDisplay for $t {
-            fn fmt_display(&self) -> String {
-                format!("{self:?}")
+            fn fmt_display_into(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                write!(out, "{self:?}")
                 // NOTE: Do not use the Display impl.
-                // format!("{self}")
+                // write!(out, "{self}")
             }
         }
     };
@@ -83,221 +207,900 @@ pydebug_pydisplay!(i16);
 pydebug_pydisplay!(i32);
 pydebug_pydisplay!(i64);
 pydebug_pydisplay!(i128);
+pydebug_pydisplay!(isize);
+
+/// Implement `PyDebug` and `PyDisplay` with `$format(&self, out, debug)`, where `debug` tells
+/// which one is implemented, as the [`PythonStyle`] may only apply to one of them. The value is
+/// displayed on its own with `$str(&self, out)`, if given.
+macro_rules! impl_styled {
+    ($t:ty, $format:expr $(, $str:expr)?) => {
+        impl PyDebug for $t {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                $format(self, out, true)
+            }
+        }
+
+        impl PyDisplay for $t {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                $format(self, out, false)
+            }
+
+            $(
+                fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    $str(self, out)
+                }
+            )?
+        }
+    };
+}
 
-pydebug_pydisplay!(f32);
-pydebug_pydisplay!(f64);
+/// Whether the [`PythonStyle`] applies to `PyDebug` if `debug`, or else to `PyDisplay`.
+fn python_style(debug: bool) -> bool {
+    FormatConfig::current().python_style.applies(debug)
+}
 
-pydebug_pydisplay!(bool);
+fn format_bool(b: &bool, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    match (python_style(debug), b) {
+        (true, true) => out.write_str("True"),
+        (true, false) => out.write_str("False"),
+        (false, _) => write!(out, "{b:?}"),
+    }
+}
 
-pydebug_pydisplay!(String);
-pydebug_pydisplay!(&str);
-pydebug_pydisplay!(char);
+fn format_float<F: fmt::Debug + fmt::LowerExp>(
+    x: &F,
+    out: &mut dyn fmt::Write,
+    debug: bool,
+) -> fmt::Result {
+    if python_style(debug) {
+        out.write_str(&repr_float(&format!("{x:e}")))
+    } else {
+        write!(out, "{x:?}")
+    }
+}
 
-fn do_format(start: char, end: char, iter: impl Iterator<Item = String>) -> String {
-    let n = ELLIPSIS_CHAR_N.load(Ordering::Relaxed);
-    let mut res = "".to_string();
-    for x in iter {
-        // + 2 is for the sides
-        if res.len() + 2 + x.len() <= n {
-            res += &format!("{x}, ");
+/// Strings are shortened to [`ReprLimits::max_string`] characters, or further so that they fit
+/// in the budget which is left, quotes included. A string is only shortened once, in the middle,
+/// so that it keeps its closing quote.
+fn format_str(s: &str, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    let config = FormatConfig::current();
+    let room = budget();
+    let mut max = config.limits.max_string;
+    loop {
+        let shortened = shorten_middle(s, max, &config.truncation_marker);
+        let quoted = if config.python_style.applies(debug) {
+            let mut quoted = String::new();
+            repr_str(&mut quoted, &shortened)?;
+            quoted
         } else {
-            res += "..., ";
-            break;
+            format!("{shortened:?}")
+        };
+        let len = quoted.chars().count();
+        if len <= room || max == 0 {
+            return out.write_str(&quoted);
+        }
+        // The quotes and escapes make the output longer than the string, in proportion
+        max = (shortened.chars().count().saturating_mul(room) / len).min(max - 1);
+    }
+}
+
+fn format_char(c: &char, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    // Python has no characters, only strings of one character
+    if python_style(debug) {
+        repr_str(out, c.encode_utf8(&mut [0; 4]))
+    } else {
+        write!(out, "{c:?}")
+    }
+}
+
+impl_styled!(f32, format_float);
+impl_styled!(f64, format_float);
+
+impl_styled!(bool, format_bool);
+
+/// Strings are displayed without quotes on their own, but still shortened.
+fn str_unquoted(s: &str, out: &mut dyn fmt::Write) -> fmt::Result {
+    let config = FormatConfig::current();
+    out.write_str(&shorten_middle(
+        s,
+        config.limits.max_string,
+        &config.truncation_marker,
+    ))
+}
+
+/// Implement `PyDebug` and `PyDisplay` for string-like types, which are only quoted when nested.
+macro_rules! impl_str {
+    ($t:ty, $format:expr, $unquoted:expr) => {
+        impl PyDebug for $t {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                $format(self, out, true)
+            }
+        }
+
+        impl PyDisplay for $t {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                $format(self, out, false)
+            }
+
+            fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                $unquoted(self, out)
+            }
+        }
+    };
+}
+
+impl_str!(char, format_char, |c: &char, out| str_unquoted(
+    c.encode_utf8(&mut [0; 4]),
+    out
+));
+impl_str!(String, format_str, str_unquoted);
+impl_str!(str, format_str, str_unquoted);
+impl_str!(&str, format_str, str_unquoted);
+
+/// Python has no unit type, functions returning nothing return `None`.
+fn format_unit(_: &(), out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    if python_style(debug) {
+        out.write_str("None")
+    } else {
+        out.write_str("()")
+    }
+}
+
+impl_styled!((), format_unit);
+
+/// A `Duration` is displayed as the `timedelta` PyO3 converts it to.
+fn format_duration(d: &Duration, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    if python_style(debug) {
+        repr_timedelta(out, false, d.as_secs(), d.subsec_nanos())
+    } else {
+        write!(out, "{d:?}")
+    }
+}
+
+fn str_duration(d: &Duration, out: &mut dyn fmt::Write) -> fmt::Result {
+    if python_style(false) {
+        str_timedelta(out, d.as_secs(), d.subsec_nanos())
+    } else {
+        format_duration(d, out, false)
+    }
+}
+
+impl_styled!(Duration, format_duration, str_duration);
+
+/// A `SystemTime` is displayed as the `datetime` in UTC PyO3 converts it to, if it is within
+/// the years Python supports.
+fn system_datetime(t: &SystemTime) -> Option<DateTime> {
+    let datetime = match t.duration_since(UNIX_EPOCH) {
+        Ok(after) => {
+            DateTime::from_unix(i64::try_from(after.as_secs()).ok()?, after.subsec_nanos())
+        }
+        Err(err) => {
+            let before = err.duration();
+            let secs = i64::try_from(before.as_secs()).ok()?.checked_neg()?;
+            let (secs, nanos) = match before.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs.checked_sub(1)?, 1_000_000_000 - nanos),
+            };
+            DateTime::from_unix(secs, nanos)
+        }
+    };
+    Some(datetime).filter(Temporal::in_range)
+}
+
+fn format_system_time(t: &SystemTime, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    match system_datetime(t) {
+        Some(datetime) if python_style(debug) => datetime.write_repr(out),
+        _ => write!(out, "{t:?}"),
+    }
+}
+
+fn str_system_time(t: &SystemTime, out: &mut dyn fmt::Write) -> fmt::Result {
+    match system_datetime(t) {
+        Some(datetime) if python_style(false) => datetime.write_iso_with(out, ' '),
+        _ => format_system_time(t, out, false),
+    }
+}
+
+impl_styled!(SystemTime, format_system_time, str_system_time);
+
+/// A path is displayed as a `pathlib` path, eg. `PosixPath('/x')`, and without quotes on its
+/// own just like a string.
+fn format_path(path: &Path, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    let config = FormatConfig::current();
+    if !config.python_style.applies(debug) {
+        return write!(out, "{path:?}");
+    }
+    let path = path.to_string_lossy();
+    // Python displays Windows paths with forward slashes
+    let (name, path) = if cfg!(windows) {
+        ("WindowsPath", Cow::Owned(path.replace('\\', "/")))
+    } else {
+        ("PosixPath", path)
+    };
+    let path = shorten_middle(&path, config.limits.max_string, &config.truncation_marker);
+    out.write_str(name)?;
+    out.write_char('(')?;
+    repr_str(out, &path)?;
+    out.write_char(')')
+}
+
+fn str_path(path: &Path, out: &mut dyn fmt::Write) -> fmt::Result {
+    str_unquoted(&path.to_string_lossy(), out)
+}
+
+impl_styled!(Path, format_path, str_path);
+impl_styled!(PathBuf, format_path, str_path);
+
+/// An IP address is displayed as an `ipaddress` address, eg. `IPv4Address('127.0.0.1')`, and
+/// without quotes on its own.
+fn format_ip(ip: IpAddr, out: &mut dyn fmt::Write, debug: bool) -> fmt::Result {
+    if !python_style(debug) {
+        return write!(out, "{ip:?}");
+    }
+    let name = match ip {
+        IpAddr::V4(_) => "IPv4Address",
+        IpAddr::V6(_) => "IPv6Address",
+    };
+    write!(out, "{name}('{ip}')")
+}
+
+fn str_ip(ip: IpAddr, out: &mut dyn fmt::Write) -> fmt::Result {
+    write!(out, "{ip}")
+}
+
+impl_styled!(
+    IpAddr,
+    |ip: &IpAddr, out, debug| format_ip(*ip, out, debug),
+    |ip: &IpAddr, out| str_ip(*ip, out)
+);
+impl_styled!(
+    Ipv4Addr,
+    |ip: &Ipv4Addr, out, debug| format_ip((*ip).into(), out, debug),
+    |ip: &Ipv4Addr, out| str_ip((*ip).into(), out)
+);
+impl_styled!(
+    Ipv6Addr,
+    |ip: &Ipv6Addr, out, debug| format_ip((*ip).into(), out, debug),
+    |ip: &Ipv6Addr, out| str_ip((*ip).into(), out)
+);
+
+/// Implement `PyDebug` and `PyDisplay` for types displayed as the value `$get` returns from `$x`.
+macro_rules! impl_via {
+    (|$x:ident| $get:expr; $($t:ty),+ $(,)?) => {$(
+        impl PyDebug for $t {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                let $x = self;
+                $get.fmt_debug_into(out)
+            }
+        }
+
+        impl PyDisplay for $t {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                let $x = self;
+                $get.fmt_display_into(out)
+            }
+        }
+    )+};
+}
+
+impl_via!(
+    |x| x.get();
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+);
+
+// The value may change right after it is loaded anyway, so no ordering is needed
+impl_via!(
+    |x| x.load(Ordering::Relaxed);
+    AtomicBool, AtomicU8, AtomicU16, AtomicU32, AtomicUsize, AtomicI8, AtomicI16, AtomicI32,
+    AtomicIsize,
+);
+#[cfg(target_has_atomic = "64")]
+impl_via!(|x| x.load(Ordering::Relaxed); AtomicU64, AtomicI64);
+
+/// Format the items of a collection between `start` and `end`, displaying at most `max_items` of
+/// them, each written with `write_item`.
+///
+/// The output is at most [`FormatConfig::max_chars`] characters, or the budget left by the
+/// enclosing values if it is lower, unless that is too short for the sides and the ellipsis.
+/// Items are displayed as long as they fit, along with the ellipsis if there are more. If even
+/// the first item does not fit, it is shortened rather than left out, and a string which does
+/// not fit is shortened to the room which is left, keeping its quotes.
+///
+/// An item is only formatted as far as it may fit, with the budget which is left, and the items
+/// after the last one displayed are not formatted at all: formatting a huge collection costs as
+/// much as its output.
+fn do_format<T>(
+    out: &mut dyn fmt::Write,
+    start: &str,
+    end: &str,
+    max_items: fn(&ReprLimits) -> usize,
+    mut items: impl ExactSizeIterator<Item = T>,
+    write_item: impl Fn(T, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let config = FormatConfig::current();
+    let format = |out: &mut dyn fmt::Write| {
+        let sep = &config.separator;
+        let sep_len = sep.chars().count();
+        let marker = &config.truncation_marker;
+        // Room for the items, between the sides
+        let room = config.max_chars.min(budget()).saturating_sub(2);
+        let max_items = max_items(&config.limits);
+        // The items are written here first, as an item which does not fit is left out
+        let mut res = String::new();
+        let mut used = 0;
+        let mut shown = 0;
+        let mut left_out = false;
+        while shown < max_items && items.len() > 0 {
+            let lead = if shown > 0 { sep_len } else { 0 };
+            // Keep room for the ellipsis if this is not the last item
+            let tail = if items.len() > 1 {
+                sep_len + config.ellipsis.chars().count()
+            } else {
+                0
+            };
+            let item_room = room.saturating_sub(used + lead + tail);
+            // The items are only formatted here, with the budget which is left
+            set_budget(item_room);
+            let Some(item) = items.next() else {
+                break;
+            };
+            let mark = res.len();
+            if shown > 0 {
+                res += sep;
+            }
+            let item_start = res.len();
+            let mut limited = Limited::new(&mut res, item_room, marker);
+            let result = write_item(item, &mut limited);
+            if limited.finish(result)? && (shown > 0 || item_room < marker.chars().count()) {
+                res.truncate(mark);
+                left_out = true;
+                break;
+            }
+            used += lead + res[item_start..].chars().count();
+            shown += 1;
+        }
+        if left_out || items.len() > 0 {
+            if shown > 0 {
+                res += sep;
+            }
+            res += &config.ellipsis;
+        }
+        out.write_str(start)?;
+        out.write_str(&res)?;
+        out.write_str(end)
+    };
+    nested(out, &config, format).unwrap_or_else(|| write!(out, "{start}{}{end}", config.ellipsis))
+}
+
+/// A set, which Python displays as `set()` when it is empty as `{}` is an empty dict.
+fn format_set<T>(
+    out: &mut dyn fmt::Write,
+    debug: bool,
+    items: impl ExactSizeIterator<Item = T>,
+    write_item: impl Fn(T, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    if items.len() == 0 && python_style(debug) {
+        return out.write_str("set()");
+    }
+    do_format(out, "{", "}", |limits| limits.max_set, items, write_item)
+}
+
+/// The end of a tuple, which Python displays as `(a,)` when it has a single item.
+fn tuple_end(len: usize, debug: bool) -> &'static str {
+    if len == 1 && python_style(debug) {
+        ",)"
+    } else {
+        ")"
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident . $index:tt),+) => {
+        impl<$($name: PyDebug),+> PyDebug for ($($name,)+) {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                let items = [$(&self.$index as &dyn PyDebug),+];
+                let end = tuple_end(items.len(), true);
+                do_format(out, "(", end, |limits| limits.max_list, items.into_iter(), |x, out| x.fmt_debug_into(out))
+            }
         }
+
+        impl<$($name: PyDisplay),+> PyDisplay for ($($name,)+) {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                let items = [$(&self.$index as &dyn PyDisplay),+];
+                let end = tuple_end(items.len(), false);
+                do_format(out, "(", end, |limits| limits.max_list, items.into_iter(), |x, out| x.fmt_display_into(out))
+            }
+        }
+    };
+}
+
+impl_tuple!(A.0);
+impl_tuple!(A.0, B.1);
+impl_tuple!(A.0, B.1, C.2);
+impl_tuple!(A.0, B.1, C.2, D.3);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11);
+
+/// A dict entry, with the key and value separated by [`FormatConfig::key_separator`].
+fn dict_item(
+    out: &mut dyn fmt::Write,
+    key: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    value: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    key(out)?;
+    out.write_str(&FormatConfig::current().key_separator)?;
+    value(out)
+}
+
+impl<T: PyDebug> PyDebug for [T] {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        do_format(
+            out,
+            "[",
+            "]",
+            |limits| limits.max_list,
+            self.iter(),
+            |x, out| x.fmt_debug_into(out),
+        )
+    }
+}
+
+impl<T: PyDisplay> PyDisplay for [T] {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        do_format(
+            out,
+            "[",
+            "]",
+            |limits| limits.max_list,
+            self.iter(),
+            |x, out| x.fmt_display_into(out),
+        )
     }
-    format!("{start}{}{end}", &res[..res.len() - 2])
 }
 
 impl<T: PyDebug> PyDebug for &[T] {
-    fn fmt_debug(&self) -> String {
-        do_format('[', ']', self.iter().map(|x| x.fmt_debug()))
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).fmt_debug_into(out)
     }
 }
 
 impl<T: PyDisplay> PyDisplay for &[T] {
-    fn fmt_display(&self) -> String {
-        do_format('[', ']', self.iter().map(|x| x.fmt_display()))
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).fmt_display_into(out)
+    }
+}
+
+impl<T: PyDebug, const N: usize> PyDebug for [T; N] {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_slice().fmt_debug_into(out)
+    }
+}
+
+impl<T: PyDisplay, const N: usize> PyDisplay for [T; N] {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_slice().fmt_display_into(out)
     }
 }
 
 impl<T: PyDebug> PyDebug for Vec<T> {
-    fn fmt_debug(&self) -> String {
-        do_format('[', ']', self.iter().map(|x| x.fmt_debug()))
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_slice().fmt_debug_into(out)
     }
 }
 
 impl<T: PyDisplay> PyDisplay for Vec<T> {
-    fn fmt_display(&self) -> String {
-        do_format('[', ']', self.iter().map(|x| x.fmt_display()))
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_slice().fmt_display_into(out)
     }
 }
 
+/// Implement `PyDebug` and `PyDisplay` for a sequence which is not a slice, displayed as a list.
+/// A `BinaryHeap` is displayed in the order of its underlying array, just like a list managed
+/// by Python's `heapq`.
+macro_rules! impl_list {
+    ($($t:ident),+) => {$(
+        impl<T: PyDebug> PyDebug for $t<T> {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                do_format(
+                    out,
+                    "[",
+                    "]",
+                    |limits| limits.max_list,
+                    self.iter(),
+                    |x, out| x.fmt_debug_into(out),
+                )
+            }
+        }
+
+        impl<T: PyDisplay> PyDisplay for $t<T> {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                do_format(
+                    out,
+                    "[",
+                    "]",
+                    |limits| limits.max_list,
+                    self.iter(),
+                    |x, out| x.fmt_display_into(out),
+                )
+            }
+        }
+    )+};
+}
+
+impl_list!(VecDeque, LinkedList, BinaryHeap);
+
 impl<T: PyDebug> PyDebug for Option<T> {
-    fn fmt_debug(&self) -> String {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Some(x) => x.fmt_debug(),
-            None => "None".to_string(),
+            Some(x) => x.fmt_debug_into(out),
+            None => out.write_str("None"),
         }
     }
 }
 
 impl<T: PyDisplay> PyDisplay for Option<T> {
-    fn fmt_display(&self) -> String {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Some(x) => x.fmt_display_into(out),
+            None => out.write_str("None"),
+        }
+    }
+
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Some(x) => x.fmt_display(),
-            None => "None".to_string(),
+            Some(x) => x.fmt_str_into(out),
+            None => out.write_str("None"),
         }
     }
 }
 
-impl<T: PyDebug> PyDebug for RwLock<T> {
-    fn fmt_debug(&self) -> String {
-        match self.try_read() {
-            Ok(x) => x.fmt_debug(),
-            Err(_) => "None".to_string(),
+/// Write the value written by `write` between `{name}(` and `)`.
+fn wrapped(
+    out: &mut dyn fmt::Write,
+    name: &str,
+    write: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    out.write_str(name)?;
+    out.write_char('(')?;
+    write(out)?;
+    out.write_char(')')
+}
+
+impl<T: PyDebug, E: PyDebug> PyDebug for Result<T, E> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Ok(x) => wrapped(out, "Ok", |out| x.fmt_debug_into(out)),
+            Err(err) => wrapped(out, "Err", |out| err.fmt_debug_into(out)),
         }
     }
 }
 
-impl<T: PyDisplay> PyDisplay for RwLock<T> {
-    fn fmt_display(&self) -> String {
-        match self.try_read() {
-            Ok(x) => x.fmt_display(),
-            Err(_) => "None".to_string(),
+impl<T: PyDisplay, E: PyDisplay> PyDisplay for Result<T, E> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Ok(x) => wrapped(out, "Ok", |out| x.fmt_display_into(out)),
+            Err(err) => wrapped(out, "Err", |out| err.fmt_display_into(out)),
         }
     }
 }
 
+impl<T: PyDebug> PyDebug for RwLock<T> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        guard_recursion(self, out, |out| {
+            format_locked(
+                out,
+                || self.try_read(),
+                || self.read(),
+                |x, out| x.fmt_debug_into(out),
+            )
+        })
+    }
+}
+
+impl<T: PyDisplay> PyDisplay for RwLock<T> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        guard_recursion(self, out, |out| {
+            format_locked(
+                out,
+                || self.try_read(),
+                || self.read(),
+                |x, out| x.fmt_display_into(out),
+            )
+        })
+    }
+}
+
 impl<T: PyDebug> PyDebug for Mutex<T> {
-    fn fmt_debug(&self) -> String {
-        match self.try_lock() {
-            Ok(x) => x.fmt_debug(),
-            Err(_) => "None".to_string(),
-        }
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        guard_recursion(self, out, |out| {
+            format_locked(
+                out,
+                || self.try_lock(),
+                || self.lock(),
+                |x, out| x.fmt_debug_into(out),
+            )
+        })
     }
 }
 
 impl<T: PyDisplay> PyDisplay for Mutex<T> {
-    fn fmt_display(&self) -> String {
-        match self.try_lock() {
-            Ok(x) => x.fmt_display(),
-            Err(_) => "None".to_string(),
-        }
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        guard_recursion(self, out, |out| {
+            format_locked(
+                out,
+                || self.try_lock(),
+                || self.lock(),
+                |x, out| x.fmt_display_into(out),
+            )
+        })
     }
 }
 
-impl<T: PyDebug> PyDebug for Arc<RwLock<T>> {
-    fn fmt_debug(&self) -> String {
-        match self.try_read() {
-            Ok(x) => x.fmt_debug(),
-            Err(_) => "None".to_string(),
-        }
+impl<T: PyDebug + Copy> PyDebug for Cell<T> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.get().fmt_debug_into(out)
     }
 }
 
-impl<T: PyDisplay> PyDisplay for Arc<RwLock<T>> {
-    fn fmt_display(&self) -> String {
-        match self.try_read() {
-            Ok(x) => x.fmt_display(),
-            Err(_) => "None".to_string(),
-        }
+impl<T: PyDisplay + Copy> PyDisplay for Cell<T> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.get().fmt_display_into(out)
     }
 }
 
-impl<T: PyDebug> PyDebug for Arc<Mutex<T>> {
-    fn fmt_debug(&self) -> String {
-        match self.try_lock() {
-            Ok(x) => x.fmt_debug(),
-            Err(_) => "None".to_string(),
+/// Implement `PyDebug` and `PyDisplay` for a smart pointer, which is displayed as its value.
+macro_rules! impl_pointer {
+    ($($t:ident),+) => {$(
+        impl<T: PyDebug + ?Sized> PyDebug for $t<T> {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).fmt_debug_into(out)
+            }
+        }
+
+        impl<T: PyDisplay + ?Sized> PyDisplay for $t<T> {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).fmt_display_into(out)
+            }
+
+            fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).fmt_str_into(out)
+            }
         }
+    )+};
+}
+
+impl_pointer!(Box, Rc, Arc);
+
+impl<B: PyDebug + ToOwned + ?Sized> PyDebug for Cow<'_, B> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).fmt_debug_into(out)
     }
 }
 
-impl<T: PyDisplay> PyDisplay for Arc<Mutex<T>> {
-    fn fmt_display(&self) -> String {
-        match self.try_lock() {
-            Ok(x) => x.fmt_display(),
-            Err(_) => "None".to_string(),
+impl<B: PyDisplay + ToOwned + ?Sized> PyDisplay for Cow<'_, B> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).fmt_display_into(out)
+    }
+
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).fmt_str_into(out)
+    }
+}
+
+/// Implement `PyDebug` and `PyDisplay` for a type holding a value which may not be available,
+/// `$get` returning it if it is and `$placeholder` being displayed otherwise.
+macro_rules! impl_maybe {
+    ($t:ty, $get:expr, $placeholder:literal) => {
+        impl<T: PyDebug> PyDebug for $t {
+            fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                match $get(self).as_deref() {
+                    Some(x) => x.fmt_debug_into(out),
+                    None => out.write_str($placeholder),
+                }
+            }
         }
+
+        impl<T: PyDisplay> PyDisplay for $t {
+            fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                match $get(self).as_deref() {
+                    Some(x) => x.fmt_display_into(out),
+                    None => out.write_str($placeholder),
+                }
+            }
+
+            fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                match $get(self).as_deref() {
+                    Some(x) => x.fmt_str_into(out),
+                    None => out.write_str($placeholder),
+                }
+            }
+        }
+    };
+}
+
+impl_maybe!(rc::Weak<T>, |x: &rc::Weak<T>| x.upgrade(), "<dead>");
+impl_maybe!(sync::Weak<T>, |x: &sync::Weak<T>| x.upgrade(), "<dead>");
+impl_maybe!(OnceCell<T>, OnceCell::get, "<uninit>");
+impl_maybe!(OnceLock<T>, OnceLock::get, "<uninit>");
+
+/// A `RefCell` which is mutably borrowed elsewhere is displayed as `<borrowed>`, as its value
+/// may be in the middle of a change.
+fn format_borrowed<T: ?Sized>(
+    cell: &RefCell<T>,
+    out: &mut dyn fmt::Write,
+    write: impl FnOnce(&T, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    guard_recursion(cell, out, |out| match cell.try_borrow() {
+        Ok(value) => write(&value, out),
+        Err(_) => out.write_str("<borrowed>"),
+    })
+}
+
+impl<T: PyDebug + ?Sized> PyDebug for RefCell<T> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_borrowed(self, out, |x, out| x.fmt_debug_into(out))
     }
 }
 
-impl<T: PyDebug + Copy> PyDebug for Cell<T> {
-    fn fmt_debug(&self) -> String {
-        self.get().fmt_debug()
+impl<T: PyDisplay + ?Sized> PyDisplay for RefCell<T> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_borrowed(self, out, |x, out| x.fmt_display_into(out))
+    }
+
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_borrowed(self, out, |x, out| x.fmt_str_into(out))
     }
 }
 
-impl<T: PyDisplay + Copy> PyDisplay for Cell<T> {
-    fn fmt_display(&self) -> String {
-        self.get().fmt_display()
+/// Write Python's `repr` of `object` if `repr`, or else its `str`, or `<unrepresentable object>`
+/// if that raises.
+fn format_object(object: &Bound<'_, PyAny>, out: &mut dyn fmt::Write, repr: bool) -> fmt::Result {
+    let text = if repr { object.repr() } else { object.str() };
+    match text {
+        Ok(text) => out.write_str(&text.to_string_lossy()),
+        Err(_) => out.write_str("<unrepresentable object>"),
+    }
+}
+
+/// A Python object is displayed by Python, so that a pyclass deriving `Repr` or `Str` is
+/// displayed inline, within the output budget of the enclosing value. Like the items of a
+/// Python list, a nested object is displayed with its `repr`, and only `str()` of the object on
+/// its own uses its `str`.
+impl<T> PyDebug for Py<T> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        Python::with_gil(|py| format_object(self.bind(py).as_any(), out, true))
+    }
+}
+
+impl<T> PyDisplay for Py<T> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        Python::with_gil(|py| format_object(self.bind(py).as_any(), out, true))
+    }
+
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        Python::with_gil(|py| format_object(self.bind(py).as_any(), out, false))
+    }
+}
+
+impl<T> PyDebug for Bound<'_, T> {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_object(self.as_any(), out, true)
+    }
+}
+
+impl<T> PyDisplay for Bound<'_, T> {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_object(self.as_any(), out, true)
+    }
+
+    fn fmt_str_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_object(self.as_any(), out, false)
     }
 }
 
 impl<K: PyDebug, V: PyDebug> PyDebug for HashMap<K, V> {
-    fn fmt_debug(&self) -> String {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         do_format(
-            '{',
-            '}',
-            self.iter()
-                .map(|(k, v)| format!("{}: {}", k.fmt_debug(), v.fmt_debug())),
+            out,
+            "{",
+            "}",
+            |limits| limits.max_dict,
+            self.iter(),
+            |(k, v), out| {
+                dict_item(
+                    out,
+                    |out| k.fmt_debug_into(out),
+                    |out| v.fmt_debug_into(out),
+                )
+            },
         )
     }
 }
 
 impl<K: PyDisplay, V: PyDisplay> PyDisplay for HashMap<K, V> {
-    fn fmt_display(&self) -> String {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         do_format(
-            '{',
-            '}',
-            self.iter()
-                .map(|(k, v)| format!("{}: {}", k.fmt_display(), v.fmt_display())),
+            out,
+            "{",
+            "}",
+            |limits| limits.max_dict,
+            self.iter(),
+            |(k, v), out| {
+                dict_item(
+                    out,
+                    |out| k.fmt_display_into(out),
+                    |out| v.fmt_display_into(out),
+                )
+            },
         )
     }
 }
 
 impl<V: PyDebug> PyDebug for HashSet<V> {
-    fn fmt_debug(&self) -> String {
-        do_format('{', '}', self.iter().map(|v| v.fmt_debug()))
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_set(out, true, self.iter(), |v, out| v.fmt_debug_into(out))
     }
 }
 
 impl<V: PyDisplay> PyDisplay for HashSet<V> {
-    fn fmt_display(&self) -> String {
-        do_format('{', '}', self.iter().map(|v| v.fmt_display()))
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_set(out, false, self.iter(), |v, out| v.fmt_display_into(out))
     }
 }
 
 impl<K: PyDebug, V: PyDebug> PyDebug for BTreeMap<K, V> {
-    fn fmt_debug(&self) -> String {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         do_format(
-            '{',
-            '}',
-            self.iter()
-                .map(|(k, v)| format!("{}: {}", k.fmt_debug(), v.fmt_debug())),
+            out,
+            "{",
+            "}",
+            |limits| limits.max_dict,
+            self.iter(),
+            |(k, v), out| {
+                dict_item(
+                    out,
+                    |out| k.fmt_debug_into(out),
+                    |out| v.fmt_debug_into(out),
+                )
+            },
         )
     }
 }
 
 impl<K: PyDisplay, V: PyDisplay> PyDisplay for BTreeMap<K, V> {
-    fn fmt_display(&self) -> String {
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         do_format(
-            '{',
-            '}',
-            self.iter()
-                .map(|(k, v)| format!("{}: {}", k.fmt_display(), v.fmt_display())),
+            out,
+            "{",
+            "}",
+            |limits| limits.max_dict,
+            self.iter(),
+            |(k, v), out| {
+                dict_item(
+                    out,
+                    |out| k.fmt_display_into(out),
+                    |out| v.fmt_display_into(out),
+                )
+            },
         )
     }
 }
 
 impl<V: PyDebug> PyDebug for BTreeSet<V> {
-    fn fmt_debug(&self) -> String {
-        do_format('{', '}', self.iter().map(|v| v.fmt_debug()))
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_set(out, true, self.iter(), |v, out| v.fmt_debug_into(out))
     }
 }
 
 impl<V: PyDisplay> PyDisplay for BTreeSet<V> {
-    fn fmt_display(&self) -> String {
-        do_format('{', '}', self.iter().map(|v| v.fmt_display()))
+    fn fmt_display_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        format_set(out, false, self.iter(), |v, out| v.fmt_display_into(out))
     }
 }
@@ -0,0 +1,193 @@
+use std::{borrow::Cow, cell::Cell, fmt};
+
+use crate::FormatConfig;
+
+/// Limits on the output of `PyDebug` and `PyDisplay`, modelled on Python's `reprlib.Repr`.
+///
+/// The limits apply to the whole output: the values nested in a collection or an object share
+/// the budget of `max_total` characters of the outermost value. They are part of the
+/// [`FormatConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReprLimits {
+    /// Maximum number of characters of a string, longer strings are shortened in the middle.
+    pub max_string: usize,
+    /// Maximum number of items displayed for a list, such as a `Vec` or a slice.
+    pub max_list: usize,
+    /// Maximum number of entries displayed for a dict, such as a `HashMap`.
+    pub max_dict: usize,
+    /// Maximum number of items displayed for a set, such as a `HashSet`.
+    pub max_set: usize,
+    /// Maximum nesting of collections and objects, deeper values are displayed as `...`.
+    pub max_level: usize,
+    /// Maximum number of characters of the output, including nested values.
+    pub max_total: usize,
+}
+
+impl Default for ReprLimits {
+    fn default() -> Self {
+        Self {
+            max_string: 100,
+            max_list: 100,
+            max_dict: 100,
+            max_set: 100,
+            max_level: 32,
+            max_total: 1000,
+        }
+    }
+}
+
+thread_local! {
+    /// Nesting level of the value being formatted on this thread, 0 outside of any value.
+    static LEVEL: Cell<usize> = const { Cell::new(0) };
+    /// Number of characters left for the value being formatted on this thread.
+    static BUDGET: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Restores the level and budget of the enclosing value, even if formatting panics.
+struct Frame {
+    level: usize,
+    budget: usize,
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        LEVEL.set(self.level);
+        BUDGET.set(self.budget);
+    }
+}
+
+/// Format a value one level deeper into `out` with `format`, or return `None` if this would
+/// exceed `max_level`.
+///
+/// The outermost value starts with a budget of `max_total` characters and nested values start
+/// with the budget left by the enclosing one (see [`set_budget`]). The output is shortened to
+/// that budget, and `format` is stopped as soon as it is spent.
+pub(crate) fn nested(
+    out: &mut dyn fmt::Write,
+    config: &FormatConfig,
+    format: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> Option<fmt::Result> {
+    let limits = &config.limits;
+    let frame = Frame {
+        level: LEVEL.get(),
+        budget: BUDGET.get(),
+    };
+    if frame.level >= limits.max_level {
+        return None;
+    }
+    let budget = if frame.level == 0 {
+        limits.max_total
+    } else {
+        frame.budget
+    };
+    LEVEL.set(frame.level + 1);
+    BUDGET.set(budget);
+    let mut limited = Limited::new(out, budget, &config.truncation_marker);
+    let result = format(&mut limited);
+    drop(frame);
+    Some(limited.finish(result).map(drop))
+}
+
+/// Number of characters left for the value being formatted.
+pub(crate) fn budget() -> usize {
+    BUDGET.get()
+}
+
+/// Set the budget of the values which are nested in the value being formatted, once it has
+/// used part of its own.
+pub(crate) fn set_budget(budget: usize) {
+    BUDGET.set(budget);
+}
+
+/// Writes at most `max` characters to `out`, shortening the output with `marker` at its end if
+/// it is longer. The output is never shorter than `marker`.
+///
+/// Once the output is known to be too long, every write fails so that formatting stops early,
+/// and [`finish`](Limited::finish) tells this apart from an error of `out`.
+pub(crate) struct Limited<'a> {
+    out: &'a mut dyn fmt::Write,
+    /// Characters which can still be written through to `out`.
+    room: usize,
+    /// The characters which would be replaced by the marker, held back until the output is
+    /// known to fit.
+    held: String,
+    held_room: usize,
+    marker: &'a str,
+    exceeded: bool,
+}
+
+impl<'a> Limited<'a> {
+    pub(crate) fn new(out: &'a mut dyn fmt::Write, max: usize, marker: &'a str) -> Self {
+        let room = max.saturating_sub(marker.chars().count());
+        Self {
+            out,
+            room,
+            held: String::new(),
+            held_room: max - room,
+            marker,
+            exceeded: false,
+        }
+    }
+
+    /// Complete the output once `result` was returned by the formatting, returning whether it
+    /// was shortened.
+    pub(crate) fn finish(self, result: fmt::Result) -> Result<bool, fmt::Error> {
+        if self.exceeded {
+            return Ok(true);
+        }
+        result?;
+        self.out.write_str(&self.held)?;
+        Ok(false)
+    }
+}
+
+impl fmt::Write for Limited<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.exceeded {
+            return Err(fmt::Error);
+        }
+        // Write through as much as there is room for, and hold back the rest
+        let (through, rest) = match s.char_indices().nth(self.room) {
+            Some((end, _)) => {
+                self.room = 0;
+                s.split_at(end)
+            }
+            None => {
+                self.room -= s.chars().count();
+                (s, "")
+            }
+        };
+        self.out.write_str(through)?;
+        for c in rest.chars() {
+            if self.held_room == 0 {
+                self.exceeded = true;
+                self.out.write_str(self.marker)?;
+                return Err(fmt::Error);
+            }
+            self.held.push(c);
+            self.held_room -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Shorten `s` to `max` characters by replacing its middle with `marker`, just like `reprlib`.
+/// The output is never shorter than `marker`.
+///
+/// Only the characters which are kept are looked at, so that shortening a huge string costs as
+/// much as the output.
+pub(crate) fn shorten_middle<'a>(s: &'a str, max: usize, marker: &str) -> Cow<'a, str> {
+    if s.char_indices().nth(max).is_none() {
+        return Cow::Borrowed(s);
+    }
+    let kept = max.saturating_sub(marker.chars().count());
+    let head = kept / 2;
+    let tail = kept - head;
+    // `s` is longer than `max`, so the head and the tail do not overlap
+    let head_end = s.char_indices().nth(head).map_or(s.len(), |(i, _)| i);
+    let tail_start = match tail {
+        0 => s.len(),
+        _ => s.char_indices().nth_back(tail - 1).map_or(0, |(i, _)| i),
+    };
+    Cow::Owned(format!("{}{marker}{}", &s[..head_end], &s[tail_start..]))
+}
@@ -0,0 +1,127 @@
+use std::{cmp::Ordering, fmt};
+
+/// Python's `repr` of a float, from its shortest representation in Rust's scientific notation
+/// (`{:e}`): `nan`, `inf`, `1.0`, `0.0001`, `1e-05` or `1e+16`.
+pub(crate) fn repr_float(scientific: &str) -> String {
+    match scientific {
+        "NaN" => return "nan".to_string(),
+        "inf" => return "inf".to_string(),
+        "-inf" => return "-inf".to_string(),
+        _ => {}
+    }
+    let (mantissa, exp) = scientific
+        .split_once('e')
+        .expect("scientific notation has an exponent");
+    let exp = exp.parse::<i32>().expect("the exponent is an integer");
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => ("-", mantissa),
+        None => ("", mantissa),
+    };
+    let digits = mantissa.replace('.', "");
+    // Python switches to scientific notation outside of this range
+    if !(-4..16).contains(&exp) {
+        let (first, rest) = digits.split_at(1);
+        let rest = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{rest}")
+        };
+        let exp_sign = if exp < 0 { '-' } else { '+' };
+        return format!("{sign}{first}{rest}e{exp_sign}{:02}", exp.abs());
+    }
+    if exp < 0 {
+        let zeros = "0".repeat((-exp - 1) as usize);
+        return format!("{sign}0.{zeros}{digits}");
+    }
+    let int_len = exp as usize + 1;
+    if digits.len() <= int_len {
+        let zeros = "0".repeat(int_len - digits.len());
+        format!("{sign}{digits}{zeros}.0")
+    } else {
+        let (int, frac) = digits.split_at(int_len);
+        format!("{sign}{int}.{frac}")
+    }
+}
+
+/// The characters other than controls which Python's `str.isprintable()` rejects, as of Unicode
+/// 15.1: format characters (Cf), private use characters (Co), and separators (Zs, Zl and Zp)
+/// other than the ASCII space. Unassigned code points (Cn) are not listed, as they change with
+/// every version of Unicode.
+const NON_PRINTABLE: &[(char, char)] = &[
+    ('\u{a0}', '\u{a0}'),
+    ('\u{ad}', '\u{ad}'),
+    ('\u{600}', '\u{605}'),
+    ('\u{61c}', '\u{61c}'),
+    ('\u{6dd}', '\u{6dd}'),
+    ('\u{70f}', '\u{70f}'),
+    ('\u{890}', '\u{891}'),
+    ('\u{8e2}', '\u{8e2}'),
+    ('\u{1680}', '\u{1680}'),
+    ('\u{180e}', '\u{180e}'),
+    ('\u{2000}', '\u{200f}'),
+    ('\u{2028}', '\u{202f}'),
+    ('\u{205f}', '\u{2064}'),
+    ('\u{2066}', '\u{206f}'),
+    ('\u{3000}', '\u{3000}'),
+    ('\u{e000}', '\u{f8ff}'),
+    ('\u{feff}', '\u{feff}'),
+    ('\u{fff9}', '\u{fffb}'),
+    ('\u{110bd}', '\u{110bd}'),
+    ('\u{110cd}', '\u{110cd}'),
+    ('\u{13430}', '\u{1343f}'),
+    ('\u{1bca0}', '\u{1bca3}'),
+    ('\u{1d173}', '\u{1d17a}'),
+    ('\u{e0001}', '\u{e0001}'),
+    ('\u{e0020}', '\u{e007f}'),
+    ('\u{f0000}', '\u{ffffd}'),
+    ('\u{100000}', '\u{10fffd}'),
+];
+
+/// Whether Python's `repr` writes `c` as it is rather than escaping it.
+fn is_printable(c: char) -> bool {
+    if c.is_ascii() {
+        return !c.is_ascii_control();
+    }
+    !c.is_control()
+        && NON_PRINTABLE
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    Ordering::Less
+                } else if start > c {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_err()
+}
+
+/// Write Python's `repr` of a string: single-quoted unless it only contains single quotes, with
+/// Python's escapes for backslashes, quotes and non-printable characters (see [`is_printable`]).
+pub(crate) fn repr_str(out: &mut dyn fmt::Write, s: &str) -> fmt::Result {
+    let quote = if s.contains('\'') && !s.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    out.write_char(quote)?;
+    for c in s.chars() {
+        match c {
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if c == quote => {
+                out.write_char('\\')?;
+                out.write_char(c)?;
+            }
+            c if !is_printable(c) => match c as u32 {
+                code @ ..=0xff => write!(out, "\\x{code:02x}")?,
+                code @ ..=0xffff => write!(out, "\\u{code:04x}")?,
+                code => write!(out, "\\U{code:08x}")?,
+            },
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char(quote)
+}
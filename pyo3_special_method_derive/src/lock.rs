@@ -0,0 +1,116 @@
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{Arc, LockResult, Mutex, RwLock, TryLockError, TryLockResult},
+    thread,
+    time::{Duration, Instant},
+};
+
+use pyo3::{exceptions::PyRuntimeError, PyErr, PyResult};
+
+use crate::FormatConfig;
+
+/// Locks whose value `Getattr` and `Dict` can expose with `#[pyo3_smd(lock = "...")]`.
+///
+/// The lock is never waited for, as the thread holding it may itself be waiting for the GIL.
+pub trait PyLock {
+    type Target;
+
+    /// Call `f` with the locked value of the attribute `name`, raising a `RuntimeError` if the
+    /// lock is held elsewhere or poisoned.
+    fn try_with<R>(&self, name: &str, f: impl FnOnce(&Self::Target) -> R) -> PyResult<R>;
+}
+
+fn lock_error<G>(name: &str, err: TryLockError<G>) -> PyErr {
+    match err {
+        TryLockError::WouldBlock => PyRuntimeError::new_err(format!(
+            "cannot access '{name}', its lock is held elsewhere"
+        )),
+        TryLockError::Poisoned(_) => {
+            PyRuntimeError::new_err(format!("cannot access '{name}', its lock is poisoned"))
+        }
+    }
+}
+
+impl<T> PyLock for RwLock<T> {
+    type Target = T;
+
+    fn try_with<R>(&self, name: &str, f: impl FnOnce(&T) -> R) -> PyResult<R> {
+        match self.try_read() {
+            Ok(guard) => Ok(f(&guard)),
+            Err(err) => Err(lock_error(name, err)),
+        }
+    }
+}
+
+impl<T> PyLock for Mutex<T> {
+    type Target = T;
+
+    fn try_with<R>(&self, name: &str, f: impl FnOnce(&T) -> R) -> PyResult<R> {
+        match self.try_lock() {
+            Ok(guard) => Ok(f(&guard)),
+            Err(err) => Err(lock_error(name, err)),
+        }
+    }
+}
+
+impl<L: PyLock> PyLock for Arc<L> {
+    type Target = L::Target;
+
+    fn try_with<R>(&self, name: &str, f: impl FnOnce(&L::Target) -> R) -> PyResult<R> {
+        (**self).try_with(name, f)
+    }
+}
+
+/// How `PyDebug` and `PyDisplay` acquire a lock to format the value it holds, part of the
+/// [`FormatConfig`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LockPolicy {
+    /// Format a lock which is held elsewhere as `<locked>`. This is the default, as waiting
+    /// may deadlock if the thread holding the lock is itself waiting for the GIL.
+    #[default]
+    Try,
+    /// Wait for the lock.
+    Block,
+    /// Wait for the lock for at most the given duration, then format it as `<locked>`.
+    Timeout(Duration),
+}
+
+/// Format the value held by a lock into `out` with `format`, acquiring it according to the [`LockPolicy`].
+///
+/// A lock held elsewhere is formatted as `<locked>`, and the value of a poisoned lock is still
+/// formatted, as `<poisoned: ...>`.
+pub(crate) fn format_locked<G: Deref>(
+    out: &mut dyn fmt::Write,
+    try_acquire: impl Fn() -> TryLockResult<G>,
+    acquire: impl FnOnce() -> LockResult<G>,
+    format: impl FnOnce(&G::Target, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let policy = FormatConfig::current().lock_policy;
+    let deadline = match policy {
+        LockPolicy::Timeout(timeout) => Instant::now().checked_add(timeout),
+        _ => None,
+    };
+    let guard = match (policy, deadline) {
+        (LockPolicy::Try, _) => try_acquire(),
+        (LockPolicy::Timeout(_), Some(deadline)) => loop {
+            match try_acquire() {
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_micros(100))
+                }
+                guard => break guard,
+            }
+        },
+        // A timeout too long for an `Instant` is as good as waiting forever
+        (LockPolicy::Block | LockPolicy::Timeout(_), _) => acquire().map_err(TryLockError::from),
+    };
+    match guard {
+        Ok(guard) => format(&guard, out),
+        Err(TryLockError::Poisoned(err)) => {
+            out.write_str("<poisoned: ")?;
+            format(&err.into_inner(), out)?;
+            out.write_str(">")
+        }
+        Err(TryLockError::WouldBlock) => out.write_str("<locked>"),
+    }
+}
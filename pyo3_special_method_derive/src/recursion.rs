@@ -0,0 +1,79 @@
+use std::{any::type_name, cell::RefCell, fmt};
+
+use crate::{limits::nested, FormatConfig};
+
+thread_local! {
+    /// The values being formatted on this thread, identified by their address and type as a
+    /// struct shares its address with its first field.
+    static ACTIVE: RefCell<Vec<(usize, &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes the value from [`ACTIVE`] once it is formatted, even if formatting panics.
+struct Visit;
+
+impl Drop for Visit {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| active.borrow_mut().pop());
+    }
+}
+
+/// Format `value` into `out` with `format`, unless `value` is already being formatted further
+/// up on this thread or is nested deeper than
+/// [`ReprLimits::max_level`](crate::ReprLimits::max_level), in which case
+/// [`FormatConfig::ellipsis`] is written. The output is also limited to the budget left by the
+/// enclosing values, and `format` is stopped once it is spent.
+///
+/// This is Rust's equivalent of Python's `reprlib.recursive_repr`, which makes formatting
+/// object graphs containing cycles safe. The derive macros and the implementations for locks
+/// in this crate use it, manual implementations of `PyDebug` and `PyDisplay` for types which
+/// may be part of a cycle should do so as well:
+///
+/// ```
+/// use pyo3_special_method_derive::{guard_recursion, PyDebug};
+/// use std::fmt;
+/// # use std::{cell::RefCell, rc::Rc};
+///
+/// struct Node {
+///     next: RefCell<Option<Rc<Node>>>,
+/// }
+///
+/// impl PyDebug for Node {
+///     fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+///         guard_recursion(self, out, |out| match &*self.next.borrow() {
+///             Some(next) => {
+///                 out.write_str("Node(")?;
+///                 next.fmt_debug_into(out)?;
+///                 out.write_str(")")
+///             }
+///             None => out.write_str("Node()"),
+///         })
+///     }
+/// }
+///
+/// let node = Rc::new(Node { next: RefCell::new(None) });
+/// *node.next.borrow_mut() = Some(node.clone());
+/// assert_eq!(node.fmt_debug(), "Node(...)");
+/// # node.next.borrow_mut().take();
+/// ```
+pub fn guard_recursion<T: ?Sized>(
+    value: &T,
+    out: &mut dyn fmt::Write,
+    format: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let key = (value as *const T as *const () as usize, type_name::<T>());
+    let entered = ACTIVE.with(|active| {
+        let mut active = active.borrow_mut();
+        if active.contains(&key) {
+            false
+        } else {
+            active.push(key);
+            true
+        }
+    });
+    let config = FormatConfig::current();
+    if !entered {
+        return out.write_str(&config.ellipsis);
+    }
+    let _visit = Visit;
+    nested(out, &config, format).unwrap_or_else(|| out.write_str(&config.ellipsis))
+}
@@ -0,0 +1,176 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pyo3::{prelude::*, types::PyTuple};
+
+use crate::{config::replace_thread, FormatConfig};
+
+/// The limits which can be changed from Python, those which are `None` are left unchanged.
+#[derive(Clone, Copy, Default)]
+struct Limits {
+    max_chars: Option<usize>,
+    max_items: Option<usize>,
+    max_list: Option<usize>,
+    max_dict: Option<usize>,
+    max_set: Option<usize>,
+    max_string: Option<usize>,
+    max_level: Option<usize>,
+    max_total: Option<usize>,
+}
+
+impl Limits {
+    fn apply(self, config: &FormatConfig) -> FormatConfig {
+        let mut config = config.clone();
+        config.max_chars = self.max_chars.unwrap_or(config.max_chars);
+        let limits = &mut config.limits;
+        // `max_items` sets all the collections, which may then be set one by one
+        if let Some(max_items) = self.max_items {
+            limits.max_list = max_items;
+            limits.max_dict = max_items;
+            limits.max_set = max_items;
+        }
+        limits.max_list = self.max_list.unwrap_or(limits.max_list);
+        limits.max_dict = self.max_dict.unwrap_or(limits.max_dict);
+        limits.max_set = self.max_set.unwrap_or(limits.max_set);
+        limits.max_string = self.max_string.unwrap_or(limits.max_string);
+        limits.max_level = self.max_level.unwrap_or(limits.max_level);
+        limits.max_total = self.max_total.unwrap_or(limits.max_total);
+        config
+    }
+}
+
+/// Change the limits of every thread which does not have its own configuration. Within a
+/// `limits` block, the current thread keeps the limits it entered the block with.
+#[pyfunction]
+#[pyo3(signature = (*, max_chars=None, max_items=None, max_list=None, max_dict=None, max_set=None, max_string=None, max_level=None, max_total=None))]
+#[allow(clippy::too_many_arguments)]
+fn set_limits(
+    max_chars: Option<usize>,
+    max_items: Option<usize>,
+    max_list: Option<usize>,
+    max_dict: Option<usize>,
+    max_set: Option<usize>,
+    max_string: Option<usize>,
+    max_level: Option<usize>,
+    max_total: Option<usize>,
+) {
+    let limits = Limits {
+        max_chars,
+        max_items,
+        max_list,
+        max_dict,
+        max_set,
+        max_string,
+        max_level,
+        max_total,
+    };
+    FormatConfig::update_global(|config| limits.apply(config));
+}
+
+/// The limits in effect on the current thread.
+#[pyfunction]
+fn get_limits() -> HashMap<&'static str, usize> {
+    let config = FormatConfig::current();
+    let limits = &config.limits;
+    HashMap::from([
+        ("max_chars", config.max_chars),
+        ("max_list", limits.max_list),
+        ("max_dict", limits.max_dict),
+        ("max_set", limits.max_set),
+        ("max_string", limits.max_string),
+        ("max_level", limits.max_level),
+        ("max_total", limits.max_total),
+    ])
+}
+
+/// Context manager changing the limits of the current thread until it exits.
+#[pyclass(unsendable)]
+struct LimitsScope {
+    limits: Limits,
+    previous: Option<Option<Arc<FormatConfig>>>,
+}
+
+#[pymethods]
+impl LimitsScope {
+    fn __enter__(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        let config = slf.limits.apply(&FormatConfig::current());
+        slf.previous = Some(replace_thread(Some(Arc::new(config))));
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        if let Some(previous) = self.previous.take() {
+            replace_thread(previous);
+        }
+        false
+    }
+}
+
+/// Change the limits of the current thread within a `with` block.
+#[pyfunction]
+#[pyo3(signature = (*, max_chars=None, max_items=None, max_list=None, max_dict=None, max_set=None, max_string=None, max_level=None, max_total=None))]
+#[allow(clippy::too_many_arguments)]
+fn limits(
+    max_chars: Option<usize>,
+    max_items: Option<usize>,
+    max_list: Option<usize>,
+    max_dict: Option<usize>,
+    max_set: Option<usize>,
+    max_string: Option<usize>,
+    max_level: Option<usize>,
+    max_total: Option<usize>,
+) -> LimitsScope {
+    LimitsScope {
+        limits: Limits {
+            max_chars,
+            max_items,
+            max_list,
+            max_dict,
+            max_set,
+            max_string,
+            max_level,
+            max_total,
+        },
+        previous: None,
+    }
+}
+
+/// Add a `_repr_config` submodule to `module`, to adjust the [`FormatConfig`] limits from Python:
+///
+/// ```python
+/// import mymod
+///
+/// mymod._repr_config.set_limits(max_chars=500, max_items=20)
+/// with mymod._repr_config.limits(max_string=1000):
+///     print(repr(obj))
+/// print(mymod._repr_config.get_limits())
+/// ```
+///
+/// `set_limits` changes the global configuration, while `limits` only changes the configuration
+/// of the current thread, until the `with` block exits: `set_limits` within the block only
+/// applies to the current thread once it exits. `max_items` sets `max_list`, `max_dict` and
+/// `max_set` at once.
+///
+/// ```
+/// use pyo3::prelude::*;
+///
+/// #[pymodule]
+/// fn mymod(m: &Bound<'_, PyModule>) -> PyResult<()> {
+///     pyo3_special_method_derive::add_repr_config(m)
+/// }
+/// ```
+pub fn add_repr_config(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    let repr_config = PyModule::new(py, "_repr_config")?;
+    repr_config.add_function(wrap_pyfunction!(set_limits, &repr_config)?)?;
+    repr_config.add_function(wrap_pyfunction!(get_limits, &repr_config)?)?;
+    repr_config.add_function(wrap_pyfunction!(limits, &repr_config)?)?;
+    repr_config.add_class::<LimitsScope>()?;
+    module.add_submodule(&repr_config)?;
+    // Submodules can only be imported by their full name once they are in `sys.modules`
+    let name = format!("{}._repr_config", module.name()?);
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(name, repr_config)?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use time::{Date as TimeDate, OffsetDateTime, PrimitiveDateTime, Time as TimeTime};
+
+use crate::datetime::{impl_temporal, Date, DateTime, Temporal, Time};
+
+fn date(date: &TimeDate) -> Date {
+    Date {
+        year: date.year().into(),
+        month: u8::from(date.month()).into(),
+        day: date.day().into(),
+    }
+}
+
+fn time(time: &TimeTime) -> Time {
+    Time {
+        hour: time.hour().into(),
+        minute: time.minute().into(),
+        second: time.second().into(),
+        microsecond: time.microsecond(),
+    }
+}
+
+impl_temporal!(
+    TimeDate => date,
+    TimeTime => time,
+    PrimitiveDateTime => |datetime: &PrimitiveDateTime| DateTime {
+        date: date(&datetime.date()),
+        time: time(&datetime.time()),
+        offset: None,
+    },
+    OffsetDateTime => |datetime: &OffsetDateTime| DateTime {
+        date: date(&datetime.date()),
+        time: time(&datetime.time()),
+        offset: Some(datetime.offset().whole_seconds()),
+    },
+);
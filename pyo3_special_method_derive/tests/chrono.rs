@@ -0,0 +1,62 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Event {
+    pub name: String,
+    pub at: DateTime<Utc>,
+    pub day: NaiveDate,
+}
+
+#[test]
+fn test_struct() {
+    let event = Event {
+        name: "launch".to_string(),
+        at: Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap(),
+        day: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+    };
+    assert_eq!(
+        event.fmt_debug(),
+        "Event(name='launch', at=datetime.datetime(2024, 5, 1, 12, 0, tzinfo=datetime.timezone.utc), \
+         day=datetime.date(2024, 5, 1))"
+    );
+    assert_eq!(
+        event.fmt_display(),
+        "Event(name=\"launch\", at=2024-05-01T12:00:00+00:00, day=2024-05-01)"
+    );
+}
+
+#[test]
+fn test_offset() {
+    let offset = FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap();
+    let datetime = offset
+        .with_ymd_and_hms(1999, 12, 31, 23, 59, 58)
+        .unwrap()
+        .with_nanosecond(250_000_000)
+        .unwrap();
+    assert_eq!(
+        datetime.fmt_debug(),
+        "datetime.datetime(1999, 12, 31, 23, 59, 58, 250000, \
+         tzinfo=datetime.timezone(datetime.timedelta(seconds=-19800)))"
+    );
+    assert_eq!(datetime.fmt_display(), "1999-12-31T23:59:58.250000-05:30");
+}
+
+#[test]
+fn test_naive() {
+    let time = NaiveTime::from_hms_opt(3, 4, 5).unwrap();
+    assert_eq!(time.fmt_debug(), "datetime.time(3, 4, 5)");
+    assert_eq!(time.fmt_display(), "03:04:05");
+    let datetime = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_time(time);
+    assert_eq!(
+        datetime.fmt_debug(),
+        "datetime.datetime(2024, 5, 1, 3, 4, 5)"
+    );
+    assert_eq!(datetime.fmt_display(), "2024-05-01T03:04:05");
+
+    // Python cannot represent dates after year 9999
+    let far = NaiveDate::from_ymd_opt(12_345, 1, 1).unwrap();
+    assert_eq!(far.fmt_debug(), format!("{far:?}"));
+}
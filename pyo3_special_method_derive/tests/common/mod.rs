@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use std::{cell::Cell, fmt};
+
+use pyo3_special_method_derive::{FormatConfig, FormatConfigGuard, PyDebug};
+
+thread_local! {
+    static FORMATTED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts how many times it is formatted.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Counted;
+
+impl PyDebug for Counted {
+    fn fmt_debug_into(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        FORMATTED.set(FORMATTED.get() + 1);
+        out.write_str("counted")
+    }
+}
+
+/// The output of `format` and the number of `Counted` values it formatted.
+pub fn formatted(format: impl FnOnce() -> String) -> (String, usize) {
+    FORMATTED.set(0);
+    let output = format();
+    (output, FORMATTED.get())
+}
+
+/// Use the default configuration, changed by `configure`, on the current thread until the
+/// returned guard is dropped.
+pub fn scoped(configure: impl FnOnce(&mut FormatConfig)) -> FormatConfigGuard {
+    let mut config = FormatConfig::default();
+    configure(&mut config);
+    config.scope()
+}
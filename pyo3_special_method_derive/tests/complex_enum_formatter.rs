@@ -1,14 +1,14 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Str;
 
-#[pyclass]
 #[derive(Str)]
-#[format(fmt = "Enum: {}.{}")]
+#[pyclass]
+#[pyo3_smd(fmt = "Enum: {}.{}")]
 enum Data {
     Alpha {
-        #[format(fmt = "A[{}]")]
+        #[pyo3_smd(fmt = "A[{}]")]
         x: usize,
-        #[format(fmt = "B[{}]")]
+        #[pyo3_smd(fmt = "B[{}]")]
         y: f32,
     },
 }
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dict;
 
-#[pyclass]
 #[derive(Dict)]
+#[pyclass]
 #[allow(dead_code)]
 enum Tester {
     Alpha { x: String },
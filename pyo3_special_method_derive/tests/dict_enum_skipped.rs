@@ -3,14 +3,14 @@ use std::collections::HashMap;
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dict;
 
-#[pyclass]
 #[derive(Dict)]
+#[pyclass]
 #[allow(dead_code)]
 enum Tester {
     Alpha {
         x: String,
     },
-    #[skip(Dict)]
+    #[pyo3_smd(skip(Dict))]
     Beta {
         x: String,
         y: String,
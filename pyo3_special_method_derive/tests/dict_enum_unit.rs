@@ -3,9 +3,8 @@ use std::collections::HashMap;
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dict;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Dict)]
 #[pyclass(eq, eq_int)]
-#[derive(Dict)]
 #[allow(dead_code)]
 enum Tester {
     Alpha,
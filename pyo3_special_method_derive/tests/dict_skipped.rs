@@ -3,12 +3,12 @@ use std::collections::HashMap;
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dict;
 
-#[pyclass]
 #[derive(Dict)]
+#[pyclass]
 #[allow(dead_code)]
 struct Person {
     pub name: String,
-    #[skip(Dict)]
+    #[pyo3_smd(skip(Dict))]
     pub address: String,
     location: String,
 }
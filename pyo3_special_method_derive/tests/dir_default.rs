@@ -0,0 +1,91 @@
+use pyo3::{ffi::c_str, prelude::*, types::PyDict};
+use pyo3_special_method_derive::Dir;
+
+#[derive(Dir)]
+#[pyclass(dict)]
+#[allow(dead_code)]
+struct Person {
+    pub name: String,
+    #[pyo3(get)]
+    pub age: u8,
+    address: String,
+}
+
+#[pymethods]
+impl Person {
+    fn greet(&self) -> String {
+        format!("Hello, {}", self.name)
+    }
+}
+
+#[derive(Dir)]
+#[pyclass]
+enum Shape {
+    Circle { radius: f64 },
+    Rect { width: f64, height: f64 },
+}
+
+#[test]
+fn test_dir_keeps_default_listing() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let person = Bound::new(
+            py,
+            Person {
+                name: "John Doe".to_string(),
+                age: 42,
+                address: "Address".to_string(),
+            },
+        )
+        .unwrap();
+        // Attributes of the instance itself are listed too
+        person.setattr("nickname", "Johnny").unwrap();
+        let dir = Person::__dir__(&person).unwrap();
+
+        for name in [
+            "name",
+            "age",
+            "nickname",
+            "greet",
+            "__dir__",
+            "__class__",
+            "__init_subclass__",
+        ] {
+            assert!(dir.contains(&name.to_string()), "{name} is missing");
+        }
+        assert!(!dir.contains(&"address".to_string()));
+        // Names listed by both are not repeated
+        assert_eq!(dir.iter().filter(|name| *name == "age").count(), 1);
+
+        // `dir()` goes through `__dir__`
+        let locals = PyDict::new(py);
+        locals.set_item("person", &person).unwrap();
+        let names = py
+            .eval(c_str!("dir(person)"), None, Some(&locals))
+            .unwrap()
+            .extract::<Vec<String>>()
+            .unwrap();
+        assert!(names.contains(&"name".to_string()));
+    });
+}
+
+#[test]
+fn test_dir_variant() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        // An instance of the subclass of the variant, which holds its attributes
+        let rect = Shape::Rect {
+            width: 1.0,
+            height: 2.0,
+        }
+        .into_pyobject(py)
+        .unwrap();
+        let dir = Shape::__dir__(&rect).unwrap();
+        for name in ["width", "height", "__match_args__", "variants"] {
+            assert!(dir.contains(&name.to_string()), "{name} is missing");
+        }
+        assert!(!dir.contains(&"radius".to_string()));
+    });
+}
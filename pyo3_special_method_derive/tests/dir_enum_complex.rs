@@ -1,8 +1,9 @@
-use pyo3::pyclass;
+use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Dir;
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 enum Tester {
     Alpha { x: String },
@@ -19,3 +20,23 @@ fn test_with_dir() {
 
     assert_eq!(dir, vec!["x".to_string(), "y".to_string()]);
 }
+
+#[test]
+fn test_variants() {
+    pyo3::prepare_freethreaded_python();
+
+    assert_eq!(
+        Tester::variants(),
+        vec!["Alpha".to_string(), "Beta".to_string()]
+    );
+
+    // `variants` is available on the class itself
+    let variants = Python::with_gil(|py| {
+        py.get_type::<Tester>()
+            .call_method0("variants")
+            .unwrap()
+            .extract::<Vec<String>>()
+            .unwrap()
+    });
+    assert_eq!(variants, vec!["Alpha".to_string(), "Beta".to_string()]);
+}
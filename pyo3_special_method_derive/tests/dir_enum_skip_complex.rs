@@ -1,14 +1,15 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 enum Tester {
     Alpha {
         x: String,
     },
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     Beta {
         x: String,
         y: String,
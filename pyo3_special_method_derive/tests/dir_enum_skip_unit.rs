@@ -1,13 +1,13 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Dir)]
 #[pyclass(eq, eq_int)]
-#[derive(Dir)]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 enum Tester {
     Alpha,
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     Beta,
 }
 
@@ -17,3 +17,8 @@ fn test_with_dir() {
 
     assert!(dir.is_empty());
 }
+
+#[test]
+fn test_variants_skip() {
+    assert_eq!(Tester::variants(), vec!["Alpha".to_string()]);
+}
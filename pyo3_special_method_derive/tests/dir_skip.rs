@@ -1,13 +1,14 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct WithFieldSkip {
     pub dora: u32,
     pub my: String,
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     pub name: f32,
 }
 
@@ -22,15 +23,16 @@ fn test_with_dir_skip() {
     assert_eq!(vec!["dora".to_string(), "my".to_string()], dir);
 }
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct WithAllFieldsSkipped {
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     pub dora: u32,
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     pub my: String,
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     pub name: f32,
 }
 
@@ -1,8 +1,9 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct Person {
     pub name: String,
@@ -1,10 +1,16 @@
 use std::collections::HashMap;
 
-use pyo3_special_method_derive::PyDisplay;
+use pyo3_special_method_derive::{PyDebug, PyDisplay};
+
+mod common;
+
+use common::scoped;
 
 #[test]
 fn vec() {
-    assert_eq!((0..150).collect::<Vec<_>>().fmt_display().len(), 103,);
+    let repr = (0..150).collect::<Vec<_>>().fmt_display();
+    assert_eq!(repr.chars().count(), 99);
+    assert!(repr.ends_with(", 24, 25, ...]"));
 }
 
 #[test]
@@ -13,5 +19,61 @@ fn hashmap() {
     for i in 0..150 {
         map.insert(format!("{i:0w$}", w = 5), format!("{i:0w$}", w = 5));
     }
-    assert_eq!(map.fmt_display().len(), 95,);
+    let repr = map.fmt_display();
+    // 5 entries of 16 chars and their separators, the ellipsis and the sides
+    assert_eq!(repr.chars().count(), 5 * 16 + 5 * 2 + 3 + 2);
+    assert!(repr.ends_with(", ...}"));
+}
+
+#[test]
+fn exact_bounds() {
+    let items = (0..10).collect::<Vec<_>>();
+    let _scope = scoped(|config| config.max_chars = 14);
+    // `[0, 1, 2, ...]` is 14 chars, with one more item the ellipsis would not fit
+    assert_eq!(items.fmt_display(), "[0, 1, 2, ...]");
+    // The ellipsis is only needed if items are left out
+    assert_eq!((0..5).collect::<Vec<_>>().fmt_display(), "[0, 1, 2, ...]");
+    assert_eq!((0..4).collect::<Vec<_>>().fmt_display(), "[0, 1, 2, 3]");
+}
+
+#[test]
+fn unicode() {
+    let repr = vec!["日本語のテキスト".to_string(); 20].fmt_display();
+    // Measured in characters, not bytes
+    assert_eq!(repr.chars().count(), 100);
+    assert_eq!(repr.matches("日本語").count(), 7);
+    // The last string is shortened to the room which is left
+    assert!(repr.ends_with(", \"日本...スト\", ...]"));
+}
+
+#[test]
+fn oversized_element() {
+    let _scope = scoped(|config| config.max_chars = 20);
+    // The first item keeps room for the ellipsis, which the next item may then take
+    let repr = vec!["x".repeat(50), "y".to_string()].fmt_display();
+    assert_eq!(repr, "[\"xxxx...xxxx\", \"y\"]");
+    assert_eq!(repr.chars().count(), 20);
+    let repr = vec!["x".repeat(50), "y".repeat(5)].fmt_display();
+    assert_eq!(repr, "[\"xxxx...xxxx\", ...]");
+
+    // The string is shortened once, keeping its quotes
+    let repr = vec!["x".repeat(50)].fmt_display();
+    assert_eq!(repr, "[\"xxxxxx...xxxxxxx\"]");
+    let repr = vec!["a\n".repeat(50)].fmt_debug();
+    assert_eq!(repr, "['a\\na\\n...a\\na\\n']");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(Vec::<u8>::new().fmt_display(), "[]");
+    assert_eq!(HashMap::<u8, u8>::new().fmt_display(), "{}");
+}
+
+#[test]
+fn custom_ellipsis() {
+    let _scope = scoped(|config| {
+        config.max_chars = 10;
+        config.ellipsis = "…".to_string();
+    });
+    assert_eq!((0..10).collect::<Vec<_>>().fmt_display(), "[0, 1, …]");
 }
@@ -1,8 +1,8 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
 enum X {
     A {},
 }
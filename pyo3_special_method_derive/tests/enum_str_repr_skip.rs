@@ -1,14 +1,13 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Repr, Str};
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Str, Repr)]
 #[pyclass(eq, eq_int)]
-#[derive(Str, Repr)]
 #[allow(dead_code)]
 enum Tester {
     Alpha,
     Beta,
-    #[skip(All)]
+    #[pyo3_smd(skip)]
     Gamma,
 }
 
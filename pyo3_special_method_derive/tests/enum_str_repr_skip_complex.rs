@@ -1,8 +1,8 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Repr, Str};
 
-#[pyclass]
 #[derive(Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 enum Tester {
     Alpha {
@@ -12,7 +12,7 @@ enum Tester {
         x: u32,
         y: u32,
     },
-    #[skip(Str, Repr)]
+    #[pyo3_smd(skip(Str, Repr))]
     Gamma {
         x: u32,
         y: u32,
@@ -1,15 +1,14 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Repr, Str};
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Str, Repr)]
 #[pyclass(eq, eq_int)]
-#[derive(Str, Repr)]
 #[allow(dead_code)]
 enum Tester {
     Alpha,
-    #[skip(Str, Repr)]
+    #[pyo3_smd(skip(Str, Repr))]
     Beta,
-    #[skip(Str)]
+    #[pyo3_smd(skip(Str))]
     Gamma,
 }
 
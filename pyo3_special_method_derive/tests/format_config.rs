@@ -0,0 +1,78 @@
+use std::{collections::BTreeMap, thread};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, FormatConfig, PyDebug, PyDisplay};
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Point {
+    pub x: u8,
+    pub y: u8,
+}
+
+#[derive(AutoDisplay)]
+#[allow(dead_code)]
+enum Shape {
+    Line(Point, Point),
+}
+
+fn compact() -> FormatConfig {
+    FormatConfig {
+        max_chars: 10,
+        ellipsis: "…".to_string(),
+        separator: ",".to_string(),
+        key_separator: ":".to_string(),
+        ..FormatConfig::default()
+    }
+}
+
+#[test]
+fn test_scope() {
+    let items = (0..10).collect::<Vec<_>>();
+    let point = Point { x: 1, y: 2 };
+    {
+        let _scope = compact().scope();
+        assert_eq!(items.fmt_debug(), "[0,1,2,…]");
+        assert_eq!(BTreeMap::from([(1, 2), (3, 4)]).fmt_debug(), "{1:2,3:4}");
+        assert_eq!(point.fmt_debug(), "Point(x=1,y=2)");
+        assert_eq!(
+            Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }).fmt_display(),
+            "Shape.Line(Point(x=0,y=0),Point(x=1,y=1))"
+        );
+
+        // Scopes nest
+        {
+            let _scope = FormatConfig::default().scope();
+            assert_eq!(point.fmt_debug(), "Point(x=1, y=2)");
+        }
+        assert_eq!(point.fmt_debug(), "Point(x=1,y=2)");
+    }
+    assert_eq!(point.fmt_debug(), "Point(x=1, y=2)");
+}
+
+#[test]
+fn test_thread() {
+    let point = Point { x: 1, y: 2 };
+    compact().set_thread();
+    assert_eq!(point.fmt_display(), "Point(x=1,y=2)");
+
+    // Other threads are not affected
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(point.fmt_display(), "Point(x=1, y=2)"));
+    });
+
+    FormatConfig::reset_thread();
+    assert_eq!(point.fmt_display(), "Point(x=1, y=2)");
+}
+
+#[test]
+fn test_truncation_marker() {
+    let _scope = FormatConfig {
+        truncation_marker: "[…]".to_string(),
+        limits: pyo3_special_method_derive::ReprLimits {
+            max_string: 8,
+            ..Default::default()
+        },
+        ..FormatConfig::default()
+    }
+    .scope();
+    assert_eq!("abcdefghijkl".fmt_debug(), "'ab[…]jkl'");
+}
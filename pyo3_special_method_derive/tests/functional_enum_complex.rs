@@ -1,8 +1,9 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 enum Tester {
     Alpha { x: u32 },
@@ -1,8 +1,9 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Getattr, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
@@ -49,13 +50,14 @@ fn test_with_repr() {
     }
     .__repr__();
     assert_eq!(
-        format!("WithFields(dora=299792458, my=\"Hello world\", name={pi})"),
+        format!("WithFields(dora=299792458, my='Hello world', name={pi})"),
         res
     );
 }
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct UnitNoFields;
 
@@ -65,8 +67,8 @@ fn test_no_fields() {
     assert_eq!(Vec::<String>::new(), fields);
 }
 
-#[pyclass]
 #[derive(Getattr)]
+#[pyclass]
 #[allow(dead_code)]
 struct PrivateFields {
     x: u32,
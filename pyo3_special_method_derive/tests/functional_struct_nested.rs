@@ -1,15 +1,15 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct B {
     pub z: u32,
 }
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct A {
     pub x: u32,
@@ -1,13 +1,14 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
     my: String,
-    #[skip(Dir, Str, Repr)]
+    #[pyo3_smd(skip(Dir, Str, Repr))]
     pub name: f32,
 }
 
@@ -46,8 +47,9 @@ fn test_with_repr() {
     assert_eq!(format!("WithFields(dora=299792458)"), res);
 }
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct UnitNoFields;
 
@@ -1,8 +1,9 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
@@ -45,8 +46,9 @@ fn test_with_repr() {
     assert_eq!(format!("WithFields(dora=299792458)"), res);
 }
 
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 #[allow(dead_code)]
 struct UnitNoFields;
 
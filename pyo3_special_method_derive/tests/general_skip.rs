@@ -2,10 +2,11 @@ use pyo3::pyclass;
 use pyo3_special_method_derive::Dir;
 
 #[allow(dead_code)]
-#[pyclass]
 #[derive(Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
 struct Data {
-    #[skip(Dir)]
+    #[pyo3_smd(skip(Dir))]
     pub x: usize,
     pub y: f32,
 }
@@ -0,0 +1,109 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use pyo3::pyclass;
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDisplay, Repr, Str};
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Wrapper<T> {
+    pub inner: T,
+    pub items: Vec<T>,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+struct View<'a> {
+    pub name: &'a str,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+enum Either<L, R> {
+    Left { value: L },
+    Right { value: R },
+}
+
+// `PhantomData` has no `PyDisplay` impl, but the field is skipped so `T` is left unbounded
+#[derive(AutoDisplay)]
+struct Tagged<T> {
+    pub id: u32,
+    #[pyo3_smd(skip)]
+    pub marker: PhantomData<T>,
+}
+
+struct NotDisplayable;
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(bound = "T: PyDisplay + Copy")]
+struct Counter<T> {
+    pub count: Cell<T>,
+}
+
+#[derive(Str, Repr)]
+#[pyclass]
+struct Holder {
+    pub wrapper: Wrapper<u32>,
+    pub view: View<'static>,
+}
+
+#[test]
+fn test_generic_struct() {
+    let wrapper = Wrapper {
+        inner: 1,
+        items: vec![2, 3],
+    };
+    assert_eq!(wrapper.to_string(), "Wrapper(inner=1, items=[2, 3])");
+    assert_eq!(format!("{wrapper:?}"), "Wrapper(inner=1, items=[2, 3])");
+}
+
+#[test]
+fn test_lifetime_struct() {
+    let name = String::from("view");
+    let view = View { name: &name };
+    assert_eq!(view.to_string(), "View(name=\"view\")");
+    assert_eq!(format!("{view:?}"), "View(name='view')");
+}
+
+#[test]
+fn test_generic_enum() {
+    let left: Either<u8, String> = Either::Left { value: 1 };
+    let right: Either<u8, String> = Either::Right {
+        value: "right".to_string(),
+    };
+    assert_eq!(left.to_string(), "Either.Left(value=1)");
+    assert_eq!(format!("{right:?}"), "Either.Right(value='right')");
+}
+
+#[test]
+fn test_skipped_field_is_unbounded() {
+    let tagged = Tagged::<NotDisplayable> {
+        id: 7,
+        marker: PhantomData,
+    };
+    assert_eq!(tagged.fmt_display(), "Tagged(id=7)");
+}
+
+#[test]
+fn test_bound_override() {
+    let counter = Counter {
+        count: Cell::new(3u8),
+    };
+    assert_eq!(counter.to_string(), "Counter(count=3)");
+}
+
+#[test]
+fn test_nested_in_pyclass() {
+    let holder = Holder {
+        wrapper: Wrapper {
+            inner: 1,
+            items: vec![2],
+        },
+        view: View { name: "static" },
+    };
+    assert_eq!(
+        holder.__str__(),
+        "Holder(wrapper=Wrapper(inner=1, items=[2]), view=View(name=\"static\"))"
+    );
+    assert_eq!(
+        holder.__repr__(),
+        "Holder(wrapper=Wrapper(inner=1, items=[2]), view=View(name='static'))"
+    );
+}
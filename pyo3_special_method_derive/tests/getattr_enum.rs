@@ -1,8 +1,8 @@
 use pyo3::{pyclass, types::PyAnyMethods, Python};
 use pyo3_special_method_derive::Getattr;
 
-#[pyclass]
 #[derive(Getattr)]
+#[pyclass]
 enum Tester {
     Alpha { x: String },
     Beta { x: String, y: String },
@@ -1,13 +1,13 @@
 use pyo3::{pyclass, Python};
 use pyo3_special_method_derive::Getattr;
 
-#[pyclass]
 #[derive(Getattr)]
+#[pyclass]
 enum Tester {
     Alpha {
         x: String,
     },
-    #[skip(Getattr)]
+    #[pyo3_smd(skip(Getattr))]
     Beta {
         x: String,
         y: String,
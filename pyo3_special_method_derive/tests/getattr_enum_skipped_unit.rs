@@ -1,12 +1,11 @@
 use pyo3::{pyclass, Python};
 use pyo3_special_method_derive::Getattr;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Getattr)]
 #[pyclass(eq, eq_int)]
-#[derive(Getattr)]
 enum Tester {
     Alpha,
-    #[skip(Getattr)]
+    #[pyo3_smd(skip(Getattr))]
     Beta,
 }
 
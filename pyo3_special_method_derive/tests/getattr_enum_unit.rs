@@ -1,9 +1,8 @@
 use pyo3::{pyclass, Python};
 use pyo3_special_method_derive::Getattr;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Getattr)]
 #[pyclass(eq, eq_int)]
-#[derive(Getattr)]
 enum Tester {
     Alpha,
     Beta,
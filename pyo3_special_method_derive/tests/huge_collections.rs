@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, HashSet};
+
+use pyo3_special_method_derive::{FormatConfig, PyDebug, PyDisplay};
+
+mod common;
+
+use common::{formatted, scoped, Counted};
+
+#[test]
+fn test_nested_vec() {
+    let data = vec![vec![Counted; 1_000_000]; 1_000];
+    let (output, count) = formatted(|| data.fmt_debug());
+    assert_eq!(
+        output,
+        "[[counted, counted, counted, counted, counted, counted, counted, counted, counted, ...], [...], ...]"
+    );
+    // Only the items which may fit are formatted
+    assert_eq!(count, 12);
+}
+
+#[test]
+fn test_map() {
+    let data = (0..100_000)
+        .map(|i| (i, vec![Counted; 1_000]))
+        .collect::<BTreeMap<_, _>>();
+    let (output, count) = formatted(|| data.fmt_debug());
+    assert_eq!(
+        output,
+        "{0: [counted, counted, counted, counted, counted, counted, counted, counted, counted, ...], ...}"
+    );
+    assert_eq!(count, 10);
+}
+
+#[test]
+fn test_few_items_left() {
+    let _scope = scoped(|config| config.limits.max_list = 2);
+    let data = vec![HashSet::from([Counted]); 1_000_000];
+    let (output, count) = formatted(|| data.fmt_debug());
+    assert_eq!(output, "[{counted}, {counted}, ...]");
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_huge_string() {
+    let data = vec!["x".repeat(10_000), "y".to_string()];
+    let head = "x".repeat(48);
+    let tail = "x".repeat(49);
+    // The string is shortened once, to the room left in the list as it is less than `max_string`
+    let short = "x".repeat(44);
+    assert_eq!(
+        data.fmt_display(),
+        format!("[\"{short}...{short}\", \"y\"]")
+    );
+    assert_eq!(data.fmt_str(), data.fmt_display());
+    let _scope = scoped(|config| config.max_chars = 200);
+    assert_eq!(data.fmt_debug(), format!("['{head}...{tail}', 'y']"));
+
+    // The items after the string are not formatted once the room is spent
+    let _scope = FormatConfig::default().scope();
+    let (output, count) = formatted(|| ("x".repeat(10_000), Counted, Counted).fmt_debug());
+    assert_eq!(output, format!("('{short}...{short}', ...)"));
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_huge_string_multibyte() {
+    let _scope = scoped(|config| config.limits.max_string = 10);
+    let data = "é".repeat(10_000);
+    assert_eq!(data.fmt_debug(), "'ééé...éééé'");
+    assert_eq!(data.fmt_str(), "ééé...éééé");
+}
@@ -0,0 +1,54 @@
+#![allow(deprecated)]
+
+use pyo3::pyclass;
+use pyo3_special_method_derive::{Dir, Repr, Str};
+
+#[derive(Dir, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[format(fmt = "Legacy: {}({})")]
+#[allow(dead_code)]
+struct WithFields {
+    pub dora: u32,
+    #[pyo3_fmt_no_skip]
+    my: String,
+    #[skip(Dir, Str)]
+    #[format(fmt = "[{}]")]
+    pub name: u32,
+}
+
+#[test]
+fn test_with_dir() {
+    let dir = WithFields {
+        dora: 0,
+        my: "".to_string(),
+        name: 0,
+    }
+    .__dir__();
+    assert_eq!(vec!["dora".to_string(), "my".to_string()], dir);
+}
+
+#[test]
+fn test_with_str() {
+    let res = WithFields {
+        dora: 1,
+        my: "Hello world".to_string(),
+        name: 2,
+    }
+    .__str__();
+    assert_eq!("Legacy: WithFields(dora=1, my=\"Hello world\")", &res);
+}
+
+#[test]
+fn test_with_repr() {
+    let res = WithFields {
+        dora: 1,
+        my: "Hello world".to_string(),
+        name: 2,
+    }
+    .__repr__();
+    assert_eq!(
+        "Legacy: WithFields(dora=1, my='Hello world', name=[2])",
+        &res
+    );
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+mod common;
+
+use common::scoped;
+
+#[derive(AutoDebug)]
+struct Chain {
+    pub next: Option<Box<Chain>>,
+}
+
+fn chain(len: usize) -> Chain {
+    (1..len).fold(Chain { next: None }, |next, _| Chain {
+        next: Some(Box::new(next)),
+    })
+}
+
+#[derive(AutoDisplay)]
+struct Series {
+    pub name: String,
+    pub a: Vec<u32>,
+    pub b: Vec<u32>,
+}
+
+#[test]
+fn test_max_string() {
+    let _scope = scoped(|config| config.limits.max_string = 10);
+    assert_eq!("abcdefghij".fmt_debug(), "'abcdefghij'");
+    assert_eq!("abcdefghijklmnop".fmt_debug(), "'abc...mnop'");
+    assert_eq!(
+        "abcdefghijklmnop".to_string().fmt_display(),
+        "\"abc...mnop\""
+    );
+}
+
+#[test]
+fn test_max_items() {
+    let _scope = scoped(|config| {
+        config.limits.max_list = 3;
+        config.limits.max_dict = 1;
+        config.limits.max_set = 2;
+    });
+    assert_eq!((0..3).collect::<Vec<_>>().fmt_debug(), "[0, 1, 2]");
+    assert_eq!((0..10).collect::<Vec<_>>().fmt_debug(), "[0, 1, 2, ...]");
+    assert_eq!(BTreeMap::from([(1, 2), (3, 4)]).fmt_debug(), "{1: 2, ...}");
+    assert_eq!(BTreeSet::from([1, 2, 3]).fmt_display(), "{1, 2, ...}");
+}
+
+#[test]
+fn test_max_level() {
+    assert_eq!(
+        chain(3).fmt_debug(),
+        "Chain(next=Chain(next=Chain(next=None)))"
+    );
+
+    let _scope = scoped(|config| config.limits.max_level = 2);
+    assert_eq!(chain(3).fmt_debug(), "Chain(next=Chain(next=...))");
+    assert_eq!(chain(2).fmt_debug(), "Chain(next=Chain(next=None))");
+    assert_eq!(vec![vec![vec![1]]].fmt_debug(), "[[[...]]]");
+    assert_eq!(vec![vec![1]].fmt_debug(), "[[1]]");
+}
+
+#[test]
+fn test_max_total() {
+    let _scope = scoped(|config| config.limits.max_total = 40);
+    let series = Series {
+        name: "s".to_string(),
+        a: (0..30).collect(),
+        b: (0..30).collect(),
+    };
+    let repr = series.fmt_display();
+    assert_eq!(repr.chars().count(), 40);
+    assert!(repr.starts_with("Series(name=\"s\", a=[0, 1, 2,"));
+    assert!(repr.ends_with("..."));
+    // Nested values share the budget of the outermost one
+    let repr = vec![(0..30).collect::<Vec<_>>(), (0..30).collect()].fmt_debug();
+    assert!(repr.chars().count() <= 40);
+    assert!(repr.starts_with("[[0, 1, 2,"));
+}
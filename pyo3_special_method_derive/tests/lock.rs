@@ -0,0 +1,94 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use pyo3::{pyclass, types::PyAnyMethods, Py, Python};
+use pyo3_special_method_derive::{Dict, Getattr};
+
+#[pyclass]
+#[derive(Clone)]
+struct Inner {
+    #[pyo3(get, set)]
+    value: u32,
+}
+
+#[derive(Getattr, Dict)]
+#[pyclass]
+struct Holder {
+    #[pyo3_smd(lock = "clone")]
+    pub copied: Arc<RwLock<Inner>>,
+    #[pyo3_smd(lock = "shared")]
+    pub shared: Arc<Mutex<Py<Inner>>>,
+}
+
+fn holder(py: Python<'_>) -> Holder {
+    Holder {
+        copied: Arc::new(RwLock::new(Inner { value: 1 })),
+        shared: Arc::new(Mutex::new(Py::new(py, Inner { value: 2 }).unwrap())),
+    }
+}
+
+#[test]
+fn test_clone_mode() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let holder = holder(py);
+        let copied = holder.__getattr__("copied".to_string()).unwrap();
+        let copied = copied.bind(py);
+        assert_eq!(copied.extract::<Inner>().unwrap().value, 1);
+
+        // The value was copied, changing it leaves the field untouched
+        copied.setattr("value", 10).unwrap();
+        assert_eq!(holder.copied.read().unwrap().value, 1);
+    });
+}
+
+#[test]
+fn test_shared_mode() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let holder = holder(py);
+        let shared = holder.__dict__().unwrap().remove("shared").unwrap();
+        shared.bind(py).setattr("value", 20).unwrap();
+
+        // Both handles point to the same object
+        let inner = holder.shared.lock().unwrap();
+        assert_eq!(inner.borrow(py).value, 20);
+    });
+}
+
+#[test]
+fn test_contention() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let holder = holder(py);
+        let _guard = holder.copied.write().unwrap();
+        let err = holder.__getattr__("copied".to_string()).unwrap_err();
+        assert_eq!(
+            err.value(py).to_string(),
+            "cannot access 'copied', its lock is held elsewhere"
+        );
+    });
+}
+
+#[test]
+fn test_poisoned() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let holder = holder(py);
+        let shared = holder.shared.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = shared.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+
+        let err = holder.__dict__().unwrap_err();
+        assert_eq!(
+            err.value(py).to_string(),
+            "cannot access 'shared', its lock is poisoned"
+        );
+    });
+}
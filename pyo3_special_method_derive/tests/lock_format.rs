@@ -0,0 +1,46 @@
+use std::{
+    panic,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Account {
+    pub balance: Arc<Mutex<u32>>,
+    pub owner: Option<String>,
+}
+
+#[test]
+fn test_locked() {
+    let lock = RwLock::new(1);
+    let _guard = lock.write().unwrap();
+    assert_eq!(lock.fmt_debug(), "<locked>");
+    assert_eq!(lock.fmt_display(), "<locked>");
+
+    let account = Account {
+        balance: Arc::new(Mutex::new(100)),
+        owner: None,
+    };
+    let _guard = account.balance.lock().unwrap();
+    // Distinct from a field which is `None`
+    assert_eq!(account.fmt_debug(), "Account(balance=<locked>, owner=None)");
+}
+
+#[test]
+fn test_poisoned() {
+    let account = Account {
+        balance: Arc::new(Mutex::new(100)),
+        owner: Some("Alice".to_string()),
+    };
+    let balance = account.balance.clone();
+    let _ = panic::catch_unwind(|| {
+        let _guard = balance.lock().unwrap();
+        panic!("poison the lock");
+    });
+    assert!(account.balance.is_poisoned());
+    assert_eq!(
+        account.fmt_display(),
+        "Account(balance=<poisoned: 100>, owner=\"Alice\")"
+    );
+}
@@ -0,0 +1,68 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use pyo3_special_method_derive::{LockPolicy, PyDebug};
+
+mod common;
+
+use common::scoped;
+
+/// Hold `lock` on another thread for `held`, returning once it is acquired.
+fn hold(lock: &Arc<Mutex<u8>>, held: Duration) -> thread::JoinHandle<()> {
+    let lock = lock.clone();
+    let (locked, is_locked) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let _guard = lock.lock().unwrap();
+        locked.send(()).unwrap();
+        thread::sleep(held);
+    });
+    is_locked.recv().unwrap();
+    handle
+}
+
+#[test]
+fn test_try() {
+    let lock = Arc::new(Mutex::new(1));
+    let handle = hold(&lock, Duration::from_millis(100));
+    assert_eq!(lock.fmt_debug(), "<locked>");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_block() {
+    let _scope = scoped(|config| config.lock_policy = LockPolicy::Block);
+    let lock = Arc::new(Mutex::new(1));
+    let handle = hold(&lock, Duration::from_millis(50));
+    assert_eq!(lock.fmt_debug(), "1");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_timeout() {
+    let lock = Arc::new(Mutex::new(1));
+    {
+        let _scope =
+            scoped(|config| config.lock_policy = LockPolicy::Timeout(Duration::from_millis(10)));
+        let handle = hold(&lock, Duration::from_millis(200));
+        assert_eq!(lock.fmt_debug(), "<locked>");
+        handle.join().unwrap();
+    }
+
+    let _scope = scoped(|config| config.lock_policy = LockPolicy::Timeout(Duration::from_secs(10)));
+    let handle = hold(&lock, Duration::from_millis(50));
+    assert_eq!(lock.fmt_debug(), "1");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_endless_timeout() {
+    // Too long for a deadline, so the lock is waited for
+    let _scope = scoped(|config| config.lock_policy = LockPolicy::Timeout(Duration::MAX));
+    let lock = Arc::new(Mutex::new(1));
+    let handle = hold(&lock, Duration::from_millis(50));
+    assert_eq!(lock.fmt_debug(), "1");
+    handle.join().unwrap();
+}
@@ -0,0 +1,115 @@
+use pyo3::{ffi::c_str, prelude::*};
+use pyo3_special_method_derive::{AutoDisplay, PyDebug, PyDisplay, Repr, Str};
+
+#[derive(Str, Repr)]
+#[pyclass]
+struct Child {
+    pub name: String,
+}
+
+#[derive(Str, Repr)]
+#[pyclass]
+struct Parent {
+    pub child: Py<Child>,
+    pub callback: PyObject,
+}
+
+#[derive(AutoDisplay)]
+struct Labelled {
+    pub label: PyObject,
+}
+
+#[derive(Repr)]
+#[pyclass]
+struct Node {
+    pub name: String,
+    pub next: Option<Py<Node>>,
+}
+
+#[test]
+fn test_nested_pyclass() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let parent = Parent {
+            child: Py::new(
+                py,
+                Child {
+                    name: "kid".to_string(),
+                },
+            )
+            .unwrap(),
+            callback: py.eval(c_str!("len"), None, None).unwrap().unbind(),
+        };
+        assert_eq!(
+            parent.__repr__(),
+            "Parent(child=Child(name='kid'), callback=<built-in function len>)"
+        );
+        // Nested objects are displayed with their `repr`, just like the items of a Python list
+        assert_eq!(
+            parent.__str__(),
+            "Parent(child=Child(name='kid'), callback=<built-in function len>)"
+        );
+    });
+}
+
+#[test]
+fn test_bound() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let list = py.eval(c_str!("[1, 'a']"), None, None).unwrap();
+        assert_eq!(list.fmt_debug(), "[1, 'a']");
+        let text = py.eval(c_str!("'text'"), None, None).unwrap();
+        assert_eq!(text.fmt_debug(), "'text'");
+        // Only quoted when nested, like a Python string
+        assert_eq!(text.fmt_str(), "text");
+        assert_eq!(text.fmt_display(), "'text'");
+        assert_eq!(vec![text.clone().unbind()].fmt_debug(), "['text']");
+        let words = py.eval(c_str!("'a b'"), None, None).unwrap().unbind();
+        let list = vec![words.clone_ref(py), words.clone_ref(py)];
+        assert_eq!(list.fmt_str(), "['a b', 'a b']");
+        assert_eq!(Labelled { label: words }.fmt_str(), "Labelled(label='a b')");
+    });
+}
+
+#[test]
+fn test_unrepresentable() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let bad = py
+            .eval(
+                c_str!("type('Bad', (), {'__repr__': lambda self: 1 / 0, '__str__': lambda self: 1 / 0})()"),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(bad.fmt_debug(), "<unrepresentable object>");
+        assert_eq!(bad.fmt_display(), "<unrepresentable object>");
+        // The exception is not left behind
+        assert!(PyErr::take(py).is_none());
+    });
+}
+
+#[test]
+fn test_cycle() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let node = Py::new(
+            py,
+            Node {
+                name: "a".to_string(),
+                next: None,
+            },
+        )
+        .unwrap();
+        node.borrow_mut(py).next = Some(node.clone_ref(py));
+        assert_eq!(
+            node.bind(py).repr().unwrap().to_string(),
+            "Node(name='a', next=...)"
+        );
+        node.borrow_mut(py).next = None;
+    });
+}
@@ -0,0 +1,75 @@
+use pyo3::{pyclass, types::PyAnyMethods, Python};
+use pyo3_special_method_derive::{Dict, Dir, Getattr, Repr, Str};
+
+#[derive(Str, Repr, Getattr)]
+#[pyclass(name = "City", module = "geo")]
+struct PyCity {
+    pub name: String,
+}
+
+// `#[pyclass]` removes the `#[pyo3(...)]` field attributes, derive macros listed before it still see them
+#[derive(Str, Dir, Dict)]
+#[pyo3_smd(dir_fields_only)]
+#[pyclass(name = "Town")]
+#[allow(dead_code)]
+struct PyTown {
+    #[pyo3(get, name = "title")]
+    name: String,
+    population: u32,
+}
+
+#[derive(Dir)]
+#[pyo3_smd(dir_fields_only)]
+#[pyclass(get_all)]
+#[allow(dead_code)]
+struct Village {
+    name: String,
+}
+
+#[test]
+fn test_pyclass_name() {
+    let city = PyCity {
+        name: "Paris".to_string(),
+    };
+    assert_eq!(city.__str__(), "City(name=\"Paris\")");
+    assert_eq!(city.__repr__(), "geo.City(name='Paris')");
+}
+
+#[test]
+fn test_pyclass_name_getattr() {
+    pyo3::prepare_freethreaded_python();
+
+    let city = PyCity {
+        name: "Paris".to_string(),
+    };
+    let err = city.__getattr__("mayor".to_string()).unwrap_err();
+    let correct_err =
+        Python::with_gil(|py| err.value(py).to_string() == "'City' has no attribute 'mayor'");
+    assert!(correct_err);
+}
+
+#[test]
+fn test_pyo3_field_name() {
+    pyo3::prepare_freethreaded_python();
+
+    let town = PyTown {
+        name: "Bath".to_string(),
+        population: 94_000,
+    };
+    assert_eq!(town.__str__(), "Town(title=\"Bath\")");
+    assert_eq!(town.__dir__(), vec!["title".to_string()]);
+
+    Python::with_gil(|py| {
+        let dict = town.__dict__().unwrap();
+        assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["title"]);
+        assert_eq!(dict["title"].bind(py).extract::<String>().unwrap(), "Bath");
+    });
+}
+
+#[test]
+fn test_get_all() {
+    let village = Village {
+        name: "Hallstatt".to_string(),
+    };
+    assert_eq!(village.__dir__(), vec!["name".to_string()]);
+}
@@ -0,0 +1,94 @@
+use pyo3::{pyclass, Python};
+use pyo3_special_method_derive::{Dir, Getattr, Repr, Str};
+
+#[derive(Dir, Str, Repr, Getattr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[pyo3_smd(rename = "Human")]
+#[allow(dead_code)]
+struct Person {
+    #[pyo3_smd(rename = "full_name")]
+    pub name: String,
+    #[pyo3_smd(include, fmt = "<{}>")]
+    age: u8,
+    #[pyo3_smd(skip(Str, Dir))]
+    pub phone_num: String,
+    #[pyo3_smd(skip)]
+    pub secret: String,
+}
+
+fn person() -> Person {
+    Person {
+        name: "John Doe".to_string(),
+        age: 42,
+        phone_num: "123".to_string(),
+        secret: "hunter2".to_string(),
+    }
+}
+
+#[test]
+fn test_str() {
+    assert_eq!(
+        person().__str__(),
+        "Human(full_name=\"John Doe\", age=<42>)"
+    );
+}
+
+#[test]
+fn test_repr() {
+    assert_eq!(
+        person().__repr__(),
+        "Human(full_name='John Doe', age=<42>, phone_num='123')"
+    );
+}
+
+#[test]
+fn test_dir() {
+    assert_eq!(
+        person().__dir__(),
+        vec!["full_name".to_string(), "age".to_string()]
+    );
+}
+
+#[test]
+fn test_getattr() {
+    pyo3::prepare_freethreaded_python();
+
+    let person = person();
+    assert!(person.__getattr__("full_name".to_string()).is_ok());
+    let err = person.__getattr__("secret".to_string()).unwrap_err();
+    let correct_err =
+        Python::with_gil(|py| &err.value(py).to_string() == "'Human' has no attribute 'secret'");
+    assert!(correct_err);
+}
+
+#[derive(Str, Dir)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[pyo3_smd(fmt = "{}::{}")]
+#[allow(dead_code)]
+enum Tester {
+    #[pyo3_smd(rename = "A")]
+    Alpha {
+        #[pyo3_smd(rename = "first")]
+        x: u32,
+        #[pyo3_smd(skip(Str))]
+        y: u32,
+    },
+    #[pyo3_smd(skip(Str))]
+    Beta { x: u32 },
+}
+
+#[test]
+fn test_enum_str() {
+    assert_eq!(Tester::Alpha { x: 1, y: 2 }.__str__(), "Tester::A(first=1)");
+    assert_eq!(Tester::Beta { x: 1 }.__str__(), "Tester::<variant skipped>");
+}
+
+#[test]
+fn test_enum_dir() {
+    assert_eq!(
+        Tester::Alpha { x: 1, y: 2 }.__dir__(),
+        vec!["first".to_string(), "y".to_string()]
+    );
+}
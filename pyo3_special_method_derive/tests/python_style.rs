@@ -0,0 +1,115 @@
+use std::collections::{BTreeSet, HashSet};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay, PythonStyle};
+
+mod common;
+
+use common::scoped;
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Sample {
+    pub flag: bool,
+    pub ratio: f64,
+    pub label: String,
+    pub pair: (u8,),
+}
+
+#[test]
+fn test_primitives() {
+    assert_eq!(true.fmt_debug(), "True");
+    assert_eq!(false.fmt_debug(), "False");
+    assert_eq!('a'.fmt_debug(), "'a'");
+    assert_eq!(42u8.fmt_debug(), "42");
+    assert_eq!((-3i64).fmt_debug(), "-3");
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(f64::NAN.fmt_debug(), "nan");
+    assert_eq!(f64::INFINITY.fmt_debug(), "inf");
+    assert_eq!(f64::NEG_INFINITY.fmt_debug(), "-inf");
+    assert_eq!(1.0f64.fmt_debug(), "1.0");
+    assert_eq!((-0.0f64).fmt_debug(), "-0.0");
+    assert_eq!(0.1f64.fmt_debug(), "0.1");
+    assert_eq!(123.456f64.fmt_debug(), "123.456");
+    assert_eq!(0.0001f64.fmt_debug(), "0.0001");
+    assert_eq!(0.00001f64.fmt_debug(), "1e-05");
+    assert_eq!(1.5e-7f64.fmt_debug(), "1.5e-07");
+    assert_eq!(1e15f64.fmt_debug(), "1000000000000000.0");
+    assert_eq!(1e16f64.fmt_debug(), "1e+16");
+    assert_eq!(1.25e100f64.fmt_debug(), "1.25e+100");
+    assert_eq!(0.1f32.fmt_debug(), "0.1");
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!("text".fmt_debug(), "'text'");
+    assert_eq!("it's".fmt_debug(), "\"it's\"");
+    assert_eq!("'\"".fmt_debug(), "'\\'\"'");
+    assert_eq!("a\\b\n\t".fmt_debug(), "'a\\\\b\\n\\t'");
+    assert_eq!("\u{0}\u{7f}\u{a0}".fmt_debug(), "'\\x00\\x7f\\xa0'");
+    assert_eq!("\u{2028}".fmt_debug(), "'\\u2028'");
+    // Format, private use and separator characters are escaped like `str.isprintable()` rejects them
+    assert_eq!("a\u{200b}b\u{ad}".fmt_debug(), "'a\\u200bb\\xad'");
+    assert_eq!(
+        "\u{3000}\u{feff}\u{e000}".fmt_debug(),
+        "'\\u3000\\ufeff\\ue000'"
+    );
+    assert_eq!("\u{e0041}".fmt_debug(), "'\\U000e0041'");
+    assert_eq!("日本語 é".to_string().fmt_debug(), "'日本語 é'");
+}
+
+#[test]
+fn test_containers() {
+    assert_eq!(HashSet::<u8>::new().fmt_debug(), "set()");
+    assert_eq!(BTreeSet::from([1, 2]).fmt_debug(), "{1, 2}");
+    assert_eq!((1,).fmt_debug(), "(1,)");
+    assert_eq!((1, "a").fmt_debug(), "(1, 'a')");
+    assert_eq!(vec![Some(true), None].fmt_debug(), "[True, None]");
+}
+
+#[test]
+fn test_styles() {
+    let sample = Sample {
+        flag: true,
+        ratio: f64::NAN,
+        label: "x".to_string(),
+        pair: (1,),
+    };
+    // By default, only `PyDebug` is Python-style
+    assert_eq!(
+        sample.fmt_debug(),
+        "Sample(flag=True, ratio=nan, label='x', pair=(1,))"
+    );
+    assert_eq!(
+        sample.fmt_display(),
+        "Sample(flag=true, ratio=NaN, label=\"x\", pair=(1))"
+    );
+    assert_eq!(BTreeSet::<u8>::new().fmt_display(), "{}");
+
+    {
+        let _scope = scoped(|config| config.python_style = PythonStyle::Always);
+        assert_eq!(
+            sample.fmt_display(),
+            "Sample(flag=True, ratio=nan, label='x', pair=(1,))"
+        );
+    }
+
+    let _scope = scoped(|config| config.python_style = PythonStyle::Never);
+    assert_eq!(
+        sample.fmt_debug(),
+        "Sample(flag=true, ratio=NaN, label=\"x\", pair=(1))"
+    );
+    assert_eq!(HashSet::<u8>::new().fmt_debug(), "{}");
+}
+
+#[test]
+fn test_nested_strings_in_display() {
+    let cities = vec!["Paris".to_string()];
+    // Python's `str()` shows the `repr()` of nested strings, `PyDisplay` only with `Always`
+    assert_eq!(cities.fmt_display(), "[\"Paris\"]");
+    assert_eq!(cities.fmt_debug(), "['Paris']");
+
+    let _scope = scoped(|config| config.python_style = PythonStyle::Always);
+    assert_eq!(cities.fmt_display(), "['Paris']");
+}
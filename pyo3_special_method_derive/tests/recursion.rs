@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Node {
+    pub name: String,
+    pub next: Option<Arc<RwLock<Node>>>,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Inner {
+    pub value: u8,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Outer {
+    pub inner: Inner,
+    pub shared: Arc<Mutex<Inner>>,
+}
+
+fn node(name: &str) -> Arc<RwLock<Node>> {
+    Arc::new(RwLock::new(Node {
+        name: name.to_string(),
+        next: None,
+    }))
+}
+
+#[test]
+fn test_cycle() {
+    let a = node("a");
+    let b = node("b");
+    a.write().unwrap().next = Some(b.clone());
+    b.write().unwrap().next = Some(a.clone());
+
+    assert_eq!(
+        a.fmt_debug(),
+        "Node(name='a', next=Node(name='b', next=...))"
+    );
+    assert_eq!(
+        b.read().unwrap().fmt_display(),
+        "Node(name=\"b\", next=Node(name=\"a\", next=...))"
+    );
+
+    // Break the cycle so that the nodes are dropped
+    a.write().unwrap().next = None;
+}
+
+#[test]
+fn test_self_cycle() {
+    let a = node("a");
+    a.write().unwrap().next = Some(a.clone());
+    assert_eq!(a.fmt_display(), "Node(name=\"a\", next=...)");
+    a.write().unwrap().next = None;
+}
+
+#[test]
+fn test_shared_without_cycle() {
+    let shared = Arc::new(Mutex::new(Inner { value: 2 }));
+    let outer = Outer {
+        inner: Inner { value: 1 },
+        shared: shared.clone(),
+    };
+    // `inner` shares its address with `outer`, and `shared` may be seen from several places
+    let expected = "Outer(inner=Inner(value=1), shared=Inner(value=2))";
+    assert_eq!(outer.fmt_debug(), expected);
+    assert_eq!(vec![outer].fmt_debug(), format!("[{expected}]"));
+    assert_eq!(
+        vec![shared.clone(), shared].fmt_debug(),
+        "[Inner(value=2), Inner(value=2)]"
+    );
+}
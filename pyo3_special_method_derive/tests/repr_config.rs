@@ -0,0 +1,94 @@
+use pyo3::{
+    ffi::c_str,
+    prelude::*,
+    types::{PyCFunction, PyDict},
+};
+use pyo3_special_method_derive::{add_repr_config, FormatConfig, PyDebug};
+
+// The global configuration is only changed from this test
+#[test]
+fn test_repr_config() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "mymod").unwrap();
+        add_repr_config(&module).unwrap();
+        py.import("sys")
+            .unwrap()
+            .getattr("modules")
+            .unwrap()
+            .set_item("mymod", &module)
+            .unwrap();
+        let locals = PyDict::new(py);
+        let format = PyCFunction::new_closure(py, None, None, |_, _| {
+            (0..10).collect::<Vec<_>>().fmt_debug()
+        })
+        .unwrap();
+        locals.set_item("format", format).unwrap();
+
+        py.run(
+            c_str!(
+                "import mymod._repr_config as repr_config
+repr_config.set_limits(max_chars=500, max_items=3, max_set=5)
+global_repr = format()
+with repr_config.limits(max_list=5):
+    scoped_repr = format()
+    scoped_limits = repr_config.get_limits()
+    repr_config.set_limits(max_list=4)
+    still_scoped_repr = format()
+limits = repr_config.get_limits()
+"
+            ),
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+
+        let config = FormatConfig::global();
+        assert_eq!(config.max_chars, 500);
+        assert_eq!(config.limits.max_list, 4);
+        assert_eq!(config.limits.max_dict, 3);
+        assert_eq!(config.limits.max_set, 5);
+
+        let get = |name: &str| locals.get_item(name).unwrap().unwrap();
+        assert_eq!(
+            get("global_repr").extract::<String>().unwrap(),
+            "[0, 1, 2, ...]"
+        );
+        assert_eq!(
+            get("scoped_repr").extract::<String>().unwrap(),
+            "[0, 1, 2, 3, 4, ...]"
+        );
+        // The block keeps its limits, the global ones apply once it exits
+        assert_eq!(
+            get("still_scoped_repr").extract::<String>().unwrap(),
+            "[0, 1, 2, 3, 4, ...]"
+        );
+        let max_list = |limits: Bound<'_, PyAny>| {
+            limits
+                .get_item("max_list")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap()
+        };
+        assert_eq!(max_list(get("scoped_limits")), 5);
+        assert_eq!(max_list(get("limits")), 4);
+    });
+
+    // Updates from concurrent threads are not lost
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    FormatConfig::update_global(|config| FormatConfig {
+                        max_chars: config.max_chars + 1,
+                        ..config.clone()
+                    });
+                }
+            });
+        }
+    });
+    assert_eq!(FormatConfig::global().max_chars, 1300);
+
+    FormatConfig::default().set_global();
+}
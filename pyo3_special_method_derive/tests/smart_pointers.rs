@@ -0,0 +1,102 @@
+use std::{
+    borrow::Cow,
+    cell::{OnceCell, RefCell},
+    rc::{self, Rc},
+    sync::{Arc, OnceLock, RwLock, Weak},
+};
+
+use pyo3::prelude::*;
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay, Repr, Str};
+
+#[derive(Str, Repr)]
+#[pyclass(unsendable)]
+struct Handles {
+    pub boxed: Box<u32>,
+    pub shared: Rc<String>,
+    pub name: Arc<str>,
+    pub label: Cow<'static, str>,
+    pub state: RefCell<Vec<u8>>,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Node {
+    pub name: String,
+    pub parent: RefCell<rc::Weak<Node>>,
+    pub children: RefCell<Vec<Rc<Node>>>,
+}
+
+#[test]
+fn test_pointers() {
+    let handles = Handles {
+        boxed: Box::new(1),
+        shared: Rc::new("shared".to_string()),
+        name: Arc::from("name"),
+        label: Cow::Borrowed("label"),
+        state: RefCell::new(vec![1, 2]),
+    };
+    assert_eq!(
+        handles.__repr__(),
+        "Handles(boxed=1, shared='shared', name='name', label='label', state=[1, 2])"
+    );
+    assert_eq!(
+        handles.__str__(),
+        "Handles(boxed=1, shared=\"shared\", name=\"name\", label=\"label\", state=[1, 2])"
+    );
+
+    // On their own, strings behind a pointer are not quoted
+    assert_eq!(Box::new("text").fmt_str(), "text");
+    assert_eq!(Cow::<str>::Owned("text".to_string()).fmt_str(), "text");
+    assert_eq!(Arc::new(RwLock::new(3)).fmt_debug(), "3");
+}
+
+#[test]
+fn test_borrowed() {
+    let cell = RefCell::new(5);
+    assert_eq!(cell.fmt_debug(), "5");
+    let _guard = cell.borrow_mut();
+    assert_eq!(cell.fmt_debug(), "<borrowed>");
+    assert_eq!(cell.fmt_display(), "<borrowed>");
+}
+
+#[test]
+fn test_weak() {
+    let parent = Rc::new(Node {
+        name: "root".to_string(),
+        parent: RefCell::new(rc::Weak::new()),
+        children: RefCell::new(Vec::new()),
+    });
+    let child = Rc::new(Node {
+        name: "leaf".to_string(),
+        parent: RefCell::new(Rc::downgrade(&parent)),
+        children: RefCell::new(Vec::new()),
+    });
+    parent.children.borrow_mut().push(child.clone());
+
+    // The cycle through the parent is cut short
+    assert_eq!(
+        child.fmt_debug(),
+        "Node(name='leaf', parent=Node(name='root', parent=<dead>, children=[...]), children=[])"
+    );
+    drop(parent);
+    assert_eq!(
+        child.fmt_display(),
+        "Node(name=\"leaf\", parent=<dead>, children=[])"
+    );
+
+    let weak: Weak<u8> = Weak::new();
+    assert_eq!(weak.fmt_debug(), "<dead>");
+}
+
+#[test]
+fn test_uninit() {
+    let cell = OnceCell::new();
+    assert_eq!(cell.fmt_debug(), "<uninit>");
+    cell.set("ready".to_string()).unwrap();
+    assert_eq!(cell.fmt_debug(), "'ready'");
+    assert_eq!(cell.fmt_str(), "ready");
+
+    let lock = OnceLock::new();
+    assert_eq!(lock.fmt_display(), "<uninit>");
+    lock.set(1.5).unwrap();
+    assert_eq!(lock.fmt_display(), "1.5");
+}
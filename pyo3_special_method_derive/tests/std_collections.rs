@@ -0,0 +1,74 @@
+use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+mod common;
+
+use common::scoped;
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Grid {
+    pub origin: (i32, i32),
+    pub cells: [[u8; 2]; 2],
+    pub history: VecDeque<String>,
+    pub pending: LinkedList<bool>,
+    pub scores: Box<[f64]>,
+}
+
+#[test]
+fn test_struct() {
+    let grid = Grid {
+        origin: (0, -1),
+        cells: [[1, 2], [3, 4]],
+        history: VecDeque::from(["a".to_string(), "b".to_string()]),
+        pending: LinkedList::from([true]),
+        scores: vec![0.5, 1.0].into_boxed_slice(),
+    };
+    assert_eq!(
+        grid.fmt_debug(),
+        "Grid(origin=(0, -1), cells=[[1, 2], [3, 4]], history=['a', 'b'], pending=[True], scores=[0.5, 1.0])"
+    );
+    assert_eq!(
+        grid.fmt_display(),
+        "Grid(origin=(0, -1), cells=[[1, 2], [3, 4]], history=[\"a\", \"b\"], pending=[true], scores=[0.5, 1.0])"
+    );
+}
+
+#[test]
+fn test_tuples() {
+    assert_eq!((1,).fmt_debug(), "(1,)");
+    assert_eq!(("a", 2.5, false).fmt_debug(), "('a', 2.5, False)");
+    assert_eq!(
+        (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12).fmt_display(),
+        "(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12)"
+    );
+    let _scope = scoped(|config| config.max_chars = 20);
+    assert_eq!(
+        (100, 200, 300, 400, 500, 600, 700, 800, 900, 1000, 1100, 1200).fmt_debug(),
+        "(100, 200, 300, ...)"
+    );
+}
+
+#[test]
+fn test_sequences() {
+    let empty: [u8; 0] = [];
+    assert_eq!(empty.fmt_debug(), "[]");
+    assert_eq!([[0u8; 3]; 2].fmt_debug(), "[[0, 0, 0], [0, 0, 0]]");
+
+    let deque = (0..1000).collect::<VecDeque<u32>>();
+    assert_eq!(
+        deque.fmt_debug(),
+        (0..1000).collect::<Vec<u32>>().fmt_debug()
+    );
+
+    let mut heap = BinaryHeap::new();
+    heap.push(1);
+    assert_eq!(heap.fmt_debug(), "[1]");
+    heap.push(3);
+    heap.push(2);
+    // The underlying array, which starts with the greatest item
+    assert_eq!(heap.fmt_debug(), "[3, 1, 2]");
+
+    let boxed: Box<[&str]> = Box::new(["x", "y"]);
+    assert_eq!(boxed.fmt_display(), "[\"x\", \"y\"]");
+}
@@ -0,0 +1,125 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    num::{NonZeroI32, NonZeroUsize},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, AtomicU64},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay, PythonStyle};
+
+mod common;
+
+use common::scoped;
+
+#[derive(AutoDisplay, AutoDebug)]
+struct Job {
+    pub timeout: Duration,
+    pub model: PathBuf,
+    pub workers: NonZeroUsize,
+    pub processed: AtomicU64,
+    pub host: IpAddr,
+    pub last: Result<(), String>,
+}
+
+fn job() -> Job {
+    Job {
+        timeout: Duration::from_millis(1500),
+        model: PathBuf::from("/models/a.bin"),
+        workers: NonZeroUsize::new(4).unwrap(),
+        processed: AtomicU64::new(12),
+        host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        last: Err("timed out".to_string()),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_struct() {
+    assert_eq!(
+        job().fmt_debug(),
+        "Job(timeout=datetime.timedelta(seconds=1.5), model=PosixPath('/models/a.bin'), workers=4, \
+         processed=12, host=IPv4Address('127.0.0.1'), last=Err('timed out'))"
+    );
+    assert_eq!(
+        job().fmt_display(),
+        "Job(timeout=1.5s, model=\"/models/a.bin\", workers=4, processed=12, host=127.0.0.1, \
+         last=Err(\"timed out\"))"
+    );
+}
+
+#[test]
+fn test_duration() {
+    assert_eq!(Duration::ZERO.fmt_debug(), "datetime.timedelta(0)");
+    assert_eq!(
+        Duration::from_nanos(1_000_000_001).fmt_debug(),
+        "datetime.timedelta(seconds=1.000000001)"
+    );
+    assert_eq!(Duration::from_secs(90).fmt_str(), "90s");
+
+    let _scope = scoped(|config| config.python_style = PythonStyle::Always);
+    assert_eq!(Duration::from_millis(1500).fmt_str(), "0:00:01.500000");
+    assert_eq!(
+        Duration::from_secs(2 * 86_400 + 3661).fmt_str(),
+        "2 days, 1:01:01"
+    );
+    // Nested values are displayed with their `repr`, just like in Python
+    assert_eq!(
+        vec![Duration::from_secs(86_400)].fmt_str(),
+        "[datetime.timedelta(seconds=86400)]"
+    );
+}
+
+#[test]
+fn test_system_time() {
+    let time = UNIX_EPOCH + Duration::new(1_704_164_645, 600_000_000);
+    assert_eq!(
+        time.fmt_debug(),
+        "datetime.datetime(2024, 1, 2, 3, 4, 5, 600000, tzinfo=datetime.timezone.utc)"
+    );
+    assert_eq!(
+        (UNIX_EPOCH - Duration::from_secs(86_400)).fmt_debug(),
+        "datetime.datetime(1969, 12, 31, 0, 0, tzinfo=datetime.timezone.utc)"
+    );
+
+    let _scope = scoped(|config| config.python_style = PythonStyle::Always);
+    assert_eq!(time.fmt_str(), "2024-01-02 03:04:05.600000+00:00");
+    assert_eq!(UNIX_EPOCH.fmt_str(), "1970-01-01 00:00:00+00:00");
+    // Python cannot represent dates before year 1
+    let ancient = UNIX_EPOCH - Duration::from_secs(63_000_000_000);
+    assert_eq!(ancient.fmt_debug(), format!("{ancient:?}"));
+    // Too far from the epoch for a number of seconds, which must not overflow
+    if let Some(far) = UNIX_EPOCH.checked_sub(Duration::from_secs(i64::MAX as u64 + 1)) {
+        assert_eq!(far.fmt_debug(), format!("{far:?}"));
+        assert_eq!(far.fmt_str(), format!("{far:?}"));
+    }
+    let _ = SystemTime::now().fmt_debug();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_path() {
+    let path = Path::new("/tmp/it's");
+    assert_eq!(path.fmt_debug(), "PosixPath(\"/tmp/it's\")");
+    assert_eq!(path.fmt_display(), "\"/tmp/it's\"");
+    assert_eq!(path.fmt_str(), "/tmp/it's");
+    assert_eq!(PathBuf::from("a/b").fmt_str(), "a/b");
+}
+
+#[test]
+fn test_ip() {
+    assert_eq!(Ipv6Addr::LOCALHOST.fmt_debug(), "IPv6Address('::1')");
+    assert_eq!(Ipv4Addr::new(10, 0, 0, 1).fmt_display(), "10.0.0.1");
+    assert_eq!(IpAddr::V6(Ipv6Addr::UNSPECIFIED).fmt_str(), "::");
+}
+
+#[test]
+fn test_numbers_and_unit() {
+    assert_eq!(NonZeroI32::new(-3).unwrap().fmt_debug(), "-3");
+    assert_eq!(AtomicBool::new(true).fmt_debug(), "True");
+    assert_eq!(AtomicBool::new(true).fmt_display(), "true");
+    assert_eq!(().fmt_debug(), "None");
+    assert_eq!(().fmt_display(), "()");
+    assert_eq!(Ok::<u8, ()>(1).fmt_debug(), "Ok(1)");
+    assert_eq!(Err::<u8, ()>(()).fmt_debug(), "Err(None)");
+}
@@ -0,0 +1,58 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::{AutoDisplay, PyDebug, PyDisplay, Repr, Str};
+
+#[derive(Clone, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(transparent)]
+struct UserId(pub String);
+
+#[derive(Str, Repr)]
+#[pyclass]
+struct Account {
+    pub id: UserId,
+    pub nickname: Option<String>,
+}
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(transparent)]
+struct Tag {
+    #[pyo3_smd(fmt = "#{}")]
+    pub name: &'static str,
+    #[allow(dead_code)]
+    hidden: u8,
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!("abc".fmt_str(), "abc");
+    assert_eq!("abc".to_string().fmt_str(), "abc");
+    assert_eq!('a'.fmt_str(), "a");
+    assert_eq!(Some("abc").fmt_str(), "abc");
+    assert_eq!(None::<String>.fmt_str(), "None");
+
+    // Nested strings are quoted, just like in Python's `str(["abc"])`
+    assert_eq!("abc".fmt_display(), "\"abc\"");
+    assert_eq!(vec!["abc"].fmt_str(), "[\"abc\"]");
+    assert_eq!(vec!["abc"].fmt_debug(), "['abc']");
+}
+
+#[test]
+fn test_transparent() {
+    let id = UserId("abc".to_string());
+    assert_eq!(id.__str__(), "abc");
+    assert_eq!(id.__repr__(), "'abc'");
+
+    let account = Account {
+        id: id.clone(),
+        nickname: Some("a".to_string()),
+    };
+    assert_eq!(account.__str__(), "Account(id=\"abc\", nickname=\"a\")");
+    assert_eq!(account.__repr__(), "Account(id='abc', nickname='a')");
+
+    let tag = Tag {
+        name: "rust",
+        hidden: 0,
+    };
+    assert_eq!(tag.to_string(), "#rust");
+    assert_eq!(tag.fmt_display(), "#\"rust\"");
+}
@@ -1,13 +1,13 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Str;
 
-#[pyclass]
 #[derive(Str)]
-#[format(fmt = "Struct: {}({})")]
+#[pyclass]
+#[pyo3_smd(fmt = "Struct: {}({})")]
 struct Data {
-    #[format(fmt = "{}")]
+    #[pyo3_smd(fmt = "{}")]
     pub x: usize,
-    #[format(fmt = "[{}]")]
+    #[pyo3_smd(fmt = "[{}]")]
     pub y: f32,
 }
 
@@ -17,3 +17,18 @@ fn test_formatter_struct() {
 
     assert_eq!(data.__str__(), "Struct: Data(x=5, y=[1.23])");
 }
+
+#[derive(Str)]
+#[pyclass]
+#[pyo3_smd(fmt = "{{{}}}: {}")]
+struct Braces {
+    #[pyo3_smd(fmt = "{{{}}}")]
+    pub x: usize,
+}
+
+#[test]
+fn test_formatter_escaped_braces() {
+    let braces = Braces { x: 5 };
+
+    assert_eq!(braces.__str__(), "{Braces}: x={5}");
+}
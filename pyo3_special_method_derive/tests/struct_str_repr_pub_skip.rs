@@ -1,13 +1,13 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
     my: String,
-    #[skip(Dir, Str, Repr)]
+    #[pyo3_smd(skip(Dir, Str, Repr))]
     pub name: f32,
 }
 
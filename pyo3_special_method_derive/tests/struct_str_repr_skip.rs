@@ -1,13 +1,13 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
     pub my: String,
-    #[skip(All)]
+    #[pyo3_smd(skip)]
     pub name: f32,
 }
 
@@ -30,5 +30,5 @@ fn test_with_repr() {
         name: std::f32::consts::PI,
     }
     .__repr__();
-    assert_eq!("WithFields(dora=299792458, my=\"Hello world\")", &res);
+    assert_eq!("WithFields(dora=299792458, my='Hello world')", &res);
 }
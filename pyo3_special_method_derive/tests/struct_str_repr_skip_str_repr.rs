@@ -1,14 +1,14 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::{Dir, Repr, Str};
 
-#[pyclass]
 #[derive(Dir, Str, Repr)]
+#[pyclass]
 #[allow(dead_code)]
 struct WithFields {
     pub dora: u32,
-    #[skip(Repr)]
+    #[pyo3_smd(skip(Repr))]
     pub my: String,
-    #[skip(Str)]
+    #[pyo3_smd(skip(Str))]
     pub name: f32,
 }
 
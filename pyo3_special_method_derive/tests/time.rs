@@ -0,0 +1,44 @@
+#![cfg(feature = "time")]
+
+use pyo3_special_method_derive::{PyDebug, PyDisplay};
+use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+
+fn datetime() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_calendar_date(2024, Month::May, 1).unwrap(),
+        Time::from_hms_micro(12, 0, 0, 5).unwrap(),
+    )
+}
+
+#[test]
+fn test_offset_datetime() {
+    let utc = datetime().assume_utc();
+    assert_eq!(
+        utc.fmt_debug(),
+        "datetime.datetime(2024, 5, 1, 12, 0, 0, 5, tzinfo=datetime.timezone.utc)"
+    );
+    assert_eq!(utc.fmt_display(), "2024-05-01T12:00:00.000005+00:00");
+
+    let paris = datetime().assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+    assert_eq!(paris.fmt_display(), "2024-05-01T12:00:00.000005+02:00");
+    assert_eq!(
+        paris.fmt_debug(),
+        "datetime.datetime(2024, 5, 1, 12, 0, 0, 5, \
+         tzinfo=datetime.timezone(datetime.timedelta(seconds=7200)))"
+    );
+}
+
+#[test]
+fn test_primitive() {
+    assert_eq!(
+        datetime().fmt_debug(),
+        "datetime.datetime(2024, 5, 1, 12, 0, 0, 5)"
+    );
+    assert_eq!(datetime().date().fmt_display(), "2024-05-01");
+    assert_eq!(Time::MIDNIGHT.fmt_debug(), "datetime.time(0, 0)");
+    assert_eq!(Time::MIDNIGHT.fmt_display(), "00:00:00");
+
+    // Python cannot represent dates before year 1
+    let ancient = Date::from_calendar_date(0, Month::January, 1).unwrap();
+    assert_eq!(ancient.fmt_debug(), format!("{ancient:?}"));
+}
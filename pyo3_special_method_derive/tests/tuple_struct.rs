@@ -0,0 +1,54 @@
+use pyo3::{pyclass, types::PyAnyMethods, Python};
+use pyo3_special_method_derive::{Dict, Dir, Getattr, Repr, Str};
+
+#[derive(Dir, Getattr, Dict, Str, Repr)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+struct Point(pub f32, pub f32);
+
+#[derive(Dir, Getattr, Dict)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[allow(dead_code)]
+struct Meters(#[pyo3_smd(name = "value")] pub f64, u8);
+
+#[test]
+fn test_dir() {
+    assert_eq!(
+        Point(1., 2.).__dir__(),
+        vec!["_0".to_string(), "_1".to_string()]
+    );
+    assert_eq!(Meters(1.5, 0).__dir__(), vec!["value".to_string()]);
+}
+
+#[test]
+fn test_str_repr() {
+    // Displayed by position, as the fields have no name of their own
+    assert_eq!(Point(1., 2.).__repr__(), "Point(1.0, 2.0)");
+    assert_eq!(Point(1., 2.).__str__(), "Point(1.0, 2.0)");
+}
+
+#[test]
+fn test_getattr() {
+    pyo3::prepare_freethreaded_python();
+
+    let y = Point(1., 2.).__getattr__("_1".to_string()).unwrap();
+    let y = Python::with_gil(|py| y.bind(py).extract::<f32>().unwrap());
+    assert_eq!(y, 2.);
+
+    let err = Meters(1.5, 0).__getattr__("_1".to_string()).unwrap_err();
+    let correct_err =
+        Python::with_gil(|py| err.value(py).to_string() == "'Meters' has no attribute '_1'");
+    assert!(correct_err);
+}
+
+#[test]
+fn test_dict() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let dict = Meters(1.5, 0).__dict__().unwrap();
+        assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["value"]);
+        assert_eq!(dict["value"].bind(py).extract::<f64>().unwrap(), 1.5);
+    });
+}
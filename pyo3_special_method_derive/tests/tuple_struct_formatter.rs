@@ -1,17 +1,17 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Str;
 
-#[pyclass]
 #[derive(Str)]
-#[format(fmt = "Struct: {}({})")]
+#[pyclass]
+#[pyo3_smd(fmt = "Struct: {}({})")]
 struct Data(
-    #[format(fmt = "{}")] pub usize,
-    #[format(fmt = "[{}]")] pub f32,
+    #[pyo3_smd(fmt = "{}")] pub usize,
+    #[pyo3_smd(fmt = "[{}]")] pub f32,
 );
 
 #[test]
 fn test_formatter_struct() {
     let data = Data(5, 1.23);
 
-    assert_eq!(data.__str__(), "Struct: Data(0=5, 1=[1.23])");
+    assert_eq!(data.__str__(), "Struct: Data(5, [1.23])");
 }
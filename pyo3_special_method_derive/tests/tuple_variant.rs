@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use pyo3::{pyclass, types::PyAnyMethods, Python};
+use pyo3_special_method_derive::{Dict, Dir, Getattr, Repr, Str};
+
+#[derive(Dir, Str, Repr, Getattr, Dict)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[allow(dead_code)]
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Labeled(
+        #[pyo3_smd(fmt = "<{}>")] String,
+        #[pyo3_smd(skip(Str, Dir), rename = "sides")] u8,
+    ),
+    #[pyo3_smd(skip)]
+    Hidden(u8),
+}
+
+#[test]
+fn test_str_repr() {
+    assert_eq!(Shape::Circle(1.0).__str__(), "Shape.Circle(1.0)");
+    assert_eq!(Shape::Rect(1.0, 2.0).__repr__(), "Shape.Rect(1.0, 2.0)");
+
+    let labeled = Shape::Labeled("hexagon".to_string(), 6);
+    assert_eq!(labeled.__str__(), "Shape.Labeled(<\"hexagon\">)");
+    assert_eq!(labeled.__repr__(), "Shape.Labeled(<'hexagon'>, 6)");
+
+    assert_eq!(Shape::Hidden(0).__str__(), "Shape.<variant skipped>");
+}
+
+#[test]
+fn test_dir() {
+    assert_eq!(Shape::Circle(1.0).__dir__(), vec!["_0".to_string()]);
+    assert_eq!(
+        Shape::Rect(1.0, 2.0).__dir__(),
+        vec!["_0".to_string(), "_1".to_string()]
+    );
+    assert_eq!(
+        Shape::Labeled("hexagon".to_string(), 6).__dir__(),
+        vec!["_0".to_string()]
+    );
+    assert!(Shape::Hidden(0).__dir__().is_empty());
+}
+
+#[test]
+fn test_getattr() {
+    pyo3::prepare_freethreaded_python();
+
+    let shape = Shape::Rect(1.0, 2.0);
+    let height = shape.__getattr__("_1".to_string()).unwrap();
+    let height = Python::with_gil(|py| height.bind(py).extract::<f64>().unwrap());
+    assert_eq!(height, 2.0);
+
+    let labeled = Shape::Labeled("hexagon".to_string(), 6);
+    let sides = labeled.__getattr__("sides".to_string()).unwrap();
+    let sides = Python::with_gil(|py| sides.bind(py).extract::<u8>().unwrap());
+    assert_eq!(sides, 6);
+
+    let err = Shape::Hidden(0).__getattr__("_0".to_string()).unwrap_err();
+    let correct_err =
+        Python::with_gil(|py| err.value(py).to_string() == "'Shape.Hidden' has no attribute '_0'");
+    assert!(correct_err);
+}
+
+#[test]
+fn test_dict() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let dict = Shape::Labeled("hexagon".to_string(), 6).__dict__().unwrap();
+        let mut keys = dict.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, vec!["_0".to_string(), "sides".to_string()]);
+        assert_eq!(
+            dict["_0"].bind(py).extract::<String>().unwrap(),
+            "hexagon".to_string()
+        );
+
+        let dict: HashMap<_, _> = Shape::Hidden(0).__dict__().unwrap();
+        assert!(dict.is_empty());
+    });
+}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use pyo3::pyclass;
+use pyo3_special_method_derive::Str;
+
+#[pyclass]
+#[derive(Str)]
+struct Person {
+    #[pyo3(get, name = "full_name")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::{Repr, Str};
+
+#[derive(Str, Repr)]
+#[pyclass]
+struct Person {
+    #[pyo3_smd(fmt = "{}", fmt = "[{}]")]
+    pub name: String,
+    #[pyo3_smd(skip(Str, Str))]
+    pub address: String,
+    #[pyo3_smd(rename = "phone", name = "phone_number")]
+    pub phone: String,
+}
+
+fn main() {}
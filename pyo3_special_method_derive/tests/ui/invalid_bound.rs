@@ -0,0 +1,9 @@
+use pyo3_special_method_derive::AutoDisplay;
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(bound = "T PyDisplay")]
+struct Wrapper<T> {
+    pub inner: T,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pyo3_special_method_derive::AutoDisplay;
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(transparent)]
+struct Pair(pub u8, pub u8);
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(transparent, fmt = "{}")]
+struct Formatted(pub u8);
+
+#[derive(AutoDisplay)]
+#[pyo3_smd(transparent)]
+enum Choice {
+    A(u8),
+}
+
+fn main() {}
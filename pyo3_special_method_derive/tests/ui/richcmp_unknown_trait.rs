@@ -0,0 +1,9 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::richcmp_derive_with;
+
+#[derive(PartialEq)]
+#[pyclass]
+#[richcmp_derive_with(PartialEq, Ord)]
+struct Point(f32, f32);
+
+fn main() {}
@@ -0,0 +1,23 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::Str;
+
+#[derive(Str)]
+#[pyclass]
+#[pyo3_smd(fmt = "{}({}) {}")]
+struct Person {
+    #[pyo3_smd(fmt = "{} {}")]
+    pub name: String,
+    #[pyo3_smd(fmt = "{{{}}} {0}")]
+    pub age: u8,
+}
+
+#[derive(Str)]
+#[pyclass]
+enum Pet {
+    #[pyo3_smd(fmt = "{0}{0}")]
+    Dog(),
+    #[pyo3_smd(fmt = "{{{}}}")]
+    Cat { #[pyo3_smd(fmt = "{name} {:?}")] name: String },
+}
+
+fn main() {}
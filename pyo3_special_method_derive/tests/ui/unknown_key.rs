@@ -0,0 +1,15 @@
+#![allow(deprecated)]
+
+use pyo3::pyclass;
+use pyo3_special_method_derive::Str;
+
+#[derive(Str)]
+#[pyclass]
+struct Person {
+    #[pyo3_smd(fmt2 = "{}")]
+    pub name: String,
+    #[format(fmt2 = "{}")]
+    pub address: String,
+}
+
+fn main() {}
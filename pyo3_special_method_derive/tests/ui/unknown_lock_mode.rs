@@ -0,0 +1,13 @@
+use std::sync::{Arc, RwLock};
+
+use pyo3::pyclass;
+use pyo3_special_method_derive::Getattr;
+
+#[derive(Getattr)]
+#[pyclass]
+struct Person {
+    #[pyo3_smd(lock = "write")]
+    pub name: Arc<RwLock<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::Dir;
+
+#[derive(Dir)]
+#[pyclass]
+struct Person {
+    #[pyo3_smd(skip(Dri))]
+    pub name: String,
+}
+
+fn main() {}
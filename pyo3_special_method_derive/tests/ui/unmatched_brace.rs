@@ -0,0 +1,16 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::Str;
+
+#[derive(Str)]
+#[pyclass]
+#[pyo3_smd(fmt = "{}({)")]
+struct Person {
+    #[pyo3_smd(fmt = "{}}")]
+    pub name: String,
+    #[pyo3_smd(fmt = "{{{}}}}")]
+    pub age: u8,
+    #[pyo3_smd(fmt = "{ {} }")]
+    pub height: u8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pyo3::pyclass;
+use pyo3_special_method_derive::Getattr;
+
+#[derive(Getattr)]
+#[pyclass]
+#[pyo3_smd(skip)]
+enum Tester {
+    #[pyo3_smd(include)]
+    Alpha { x: u32 },
+}
+
+fn main() {}
@@ -1,9 +1,9 @@
 use pyo3::pyclass;
 use pyo3_special_method_derive::Str;
 
-#[pyclass(eq, eq_int)]
 #[derive(Str, PartialEq)]
-#[format(fmt = "Enum: {}.{}")]
+#[pyclass(eq, eq_int)]
+#[pyo3_smd(fmt = "Enum: {}.{}")]
 enum Data {
     Alpha,
 }
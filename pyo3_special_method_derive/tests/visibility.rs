@@ -0,0 +1,104 @@
+use pyo3::{pyclass, types::PyAnyMethods, Python};
+use pyo3_special_method_derive::{Dict, Dir, Getattr, Repr, Str};
+
+#[derive(Str, Repr, Dir, Getattr, Dict)]
+#[pyclass]
+#[pyo3_smd(dir_fields_only)]
+#[allow(dead_code)]
+struct Person {
+    pub name: String,
+    #[pyo3_smd(include)]
+    age: u8,
+    pub(crate) address: String,
+    id: u32,
+    #[pyo3_smd(skip(Dir, Str))]
+    pub email: String,
+}
+
+fn person() -> Person {
+    Person {
+        name: "John".to_string(),
+        age: 42,
+        address: "Main Street".to_string(),
+        id: 1,
+        email: "john@example.com".to_string(),
+    }
+}
+
+#[test]
+fn test_views_agree() {
+    pyo3::prepare_freethreaded_python();
+
+    let person = person();
+    assert_eq!(person.__str__(), "Person(name=\"John\", age=42)");
+    assert_eq!(
+        person.__repr__(),
+        "Person(name='John', age=42, email='john@example.com')"
+    );
+    assert_eq!(
+        person.__dir__(),
+        vec!["name".to_string(), "age".to_string()]
+    );
+
+    let mut keys = person.__dict__().unwrap().into_keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["age", "email", "name"]);
+
+    let age = person.__getattr__("age".to_string()).unwrap();
+    let age = Python::with_gil(|py| age.bind(py).extract::<u8>().unwrap());
+    assert_eq!(age, 42);
+    // Restricted visibility counts as private
+    assert!(person.__getattr__("address".to_string()).is_err());
+    assert!(person.__getattr__("id".to_string()).is_err());
+}
+
+mod inner {
+    use pyo3::pyclass;
+    use pyo3_special_method_derive::{Dict, Dir, Getattr, Repr, Str};
+
+    #[derive(Str, Repr, Dir, Getattr, Dict)]
+    #[pyclass]
+    #[pyo3_smd(dir_fields_only)]
+    #[allow(dead_code)]
+    pub struct Account {
+        pub owner: String,
+        pub(crate) balance: u32,
+        pub(super) limit: u32,
+        pub(in crate::inner) pin: u16,
+        #[pyo3_smd(include)]
+        pub(crate) currency: String,
+    }
+
+    pub fn account() -> Account {
+        Account {
+            owner: "John".to_string(),
+            balance: 10,
+            limit: 100,
+            pin: 1234,
+            currency: "EUR".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_restricted_visibility() {
+    pyo3::prepare_freethreaded_python();
+
+    // `pub(crate)`, `pub(super)` and `pub(in ...)` count as private, unless included
+    let account = inner::account();
+    assert_eq!(
+        account.__str__(),
+        "Account(owner=\"John\", currency=\"EUR\")"
+    );
+    assert_eq!(account.__repr__(), "Account(owner='John', currency='EUR')");
+    assert_eq!(
+        account.__dir__(),
+        vec!["owner".to_string(), "currency".to_string()]
+    );
+    let mut keys = account.__dict__().unwrap().into_keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["currency", "owner"]);
+    for name in ["balance", "limit", "pin"] {
+        assert!(account.__getattr__(name.to_string()).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+
+use pyo3_special_method_derive::{AutoDebug, AutoDisplay, PyDebug, PyDisplay};
+
+mod common;
+
+use common::{formatted, scoped, Counted};
+
+#[derive(AutoDebug)]
+struct Wide {
+    pub a: Counted,
+    pub b: Counted,
+    pub c: Counted,
+    pub d: Counted,
+    pub e: Counted,
+    pub f: Counted,
+}
+
+#[derive(AutoDisplay, AutoDebug)]
+enum Shape {
+    Circle { radius: u32 },
+    Rect(u32, u32),
+}
+
+/// Fails on every write.
+struct Broken;
+
+impl fmt::Write for Broken {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+#[test]
+fn test_appends_to_buffer() {
+    let mut out = "shapes: ".to_string();
+    Shape::Circle { radius: 2 }
+        .fmt_debug_into(&mut out)
+        .unwrap();
+    out += " ";
+    Shape::Rect(1, 2).fmt_display_into(&mut out).unwrap();
+    assert_eq!(out, "shapes: Shape.Circle(radius=2) Shape.Rect(1, 2)");
+}
+
+#[test]
+fn test_string_methods_agree() {
+    let shapes = vec![Shape::Rect(1, 2), Shape::Circle { radius: 3 }];
+    let mut out = String::new();
+    shapes.fmt_debug_into(&mut out).unwrap();
+    assert_eq!(out, shapes.fmt_debug());
+    assert_eq!(
+        shapes.fmt_display(),
+        "[Shape.Rect(1, 2), Shape.Circle(radius=3)]"
+    );
+}
+
+#[test]
+fn test_stops_once_budget_is_spent() {
+    let _scope = scoped(|config| config.limits.max_total = 25);
+    let (output, count) = formatted(|| {
+        Wide {
+            a: Counted,
+            b: Counted,
+            c: Counted,
+            d: Counted,
+            e: Counted,
+            f: Counted,
+        }
+        .fmt_debug()
+    });
+    assert_eq!(output, "Wide(a=counted, b=coun...");
+    // The fields after the budget is spent are not formatted
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_sink_errors_are_passed_on() {
+    assert_eq!(
+        Shape::Rect(1, 2).fmt_debug_into(&mut Broken),
+        Err(fmt::Error)
+    );
+    assert_eq!(vec![1, 2].fmt_display_into(&mut Broken), Err(fmt::Error));
+}
@@ -10,12 +10,12 @@ use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
 #[derive(Clone, AutoDisplay, PartialEq, Eq, Hash, Default, AutoDebug)]
-#[format(fmt = "")] // We don't want CityName(Paris), but directly Paris
+#[pyo3_smd(fmt = "")] // We don't want CityName(Paris), but directly Paris
 pub enum CityName {
     Paris,
     #[default]
     London,
-    #[format(fmt = "NYC the best city in the world")]
+    #[pyo3_smd(fmt = "NYC the best city in the world")]
     NewYork,
 }
 
@@ -33,7 +33,7 @@ impl FromStr for CityName {
 }
 
 #[derive(Default, AutoDisplay, AutoDebug)]
-#[format(fmt = "{}({})")]
+#[pyo3_smd(fmt = "{}({})")]
 pub struct City {
     pub name: CityName,
     addresses: HashMap<String, Arc<RwLock<PyAddress>>>,
@@ -59,8 +59,8 @@ impl City {
         self.addresses.remove(address_key);
     }
 }
-#[pyclass]
 #[derive(Str, Clone, AutoDebug)]
+#[pyclass]
 pub struct PyCity {
     pub city: Arc<RwLock<City>>, // TODO currently this printed as PyCity(city=RwLock { data: City(name=CityName(City.London), addresses={}), poisoned: false, .. })
 }
@@ -84,8 +84,8 @@ impl PyCity {
 }
 
 // Name enum, will show PyAddress.House(country=..., city=...,) etc
-#[pyclass]
 #[derive(Dir, Dict, Str, Repr, Getattr, Clone, Debug)]
+#[pyclass]
 pub enum PyAddress {
     House {
         country: String,
@@ -95,12 +95,13 @@ pub enum PyAddress {
     },
 }
 
-#[pyclass]
 #[derive(Dir, Str, Repr, Getattr, Dict, Clone)]
+#[pyclass]
 pub struct Person {
     pub name: String,
-    #[pyo3_fmt_no_skip]
+    #[pyo3_smd(include)]
     age: u8,
+    #[pyo3_smd(include, lock = "clone")]
     address: Arc<RwLock<PyAddress>>,
 }
 
@@ -216,5 +217,6 @@ fn pyo3_smd_example(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     env_logger::init();
     m.add_class::<Person>()?;
     m.add_class::<PyCity>()?;
+    pyo3_special_method_derive::add_repr_config(m)?;
     Ok(())
 }
@@ -0,0 +1,454 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parenthesized, token, Attribute, Data, DeriveInput, Expr, Ident, LitStr, Path, Token,
+    WherePredicate,
+};
+
+/// The helper attribute understood by every derive macro: `#[pyo3_smd(...)]`.
+pub(crate) const ATTR_NAMESPACE: &str = "pyo3_smd";
+/// Deprecated alias of `#[pyo3_smd(skip(...))]`.
+const ATTR_SKIP_NAMESPACE: &str = "skip";
+/// Deprecated alias of `#[pyo3_smd(include)]`.
+const ATTR_NAMESPACE_NO_FMT_SKIP: &str = "pyo3_fmt_no_skip";
+/// Deprecated alias of `#[pyo3_smd(fmt = "...")]`.
+const ATTR_NAMESPACE_FORMATTER: &str = "format";
+/// PyO3's own attributes, which hold the Python-visible names and `get` options.
+const PYO3_ATTRS: &[&str] = &["pyclass", "pyo3"];
+pub(crate) const SKIP_ALL: &str = "All";
+
+/// Every key of the `#[pyo3_smd(...)]` attribute.
+const KEYS: &[&str] = &[
+    "skip",
+    "include",
+    "fmt",
+    "rename",
+    "name",
+    "bound",
+    "dir_fields_only",
+    "lock",
+    "transparent",
+];
+/// Everything which may be passed to `skip(...)`.
+const SKIP_TARGETS: &[&str] = &[
+    SKIP_ALL,
+    "Str",
+    "Repr",
+    "Dir",
+    "Getattr",
+    "Dict",
+    "AutoDisplay",
+    "AutoDebug",
+];
+
+/// How `Getattr` and `Dict` expose a field holding a lock, see `pyo3_special_method_derive::PyLock`.
+#[derive(Clone, Copy)]
+pub(crate) enum LockMode {
+    /// `lock = "clone"`: convert a clone of the locked value.
+    Clone,
+    /// `lock = "shared"`: the lock holds a `Py<T>`, hand out another reference to it.
+    Shared,
+}
+
+/// The kind of item an attribute is attached to.
+#[derive(Clone, Copy)]
+pub(crate) enum AttrTarget {
+    Container,
+    Variant,
+    Field,
+}
+
+impl AttrTarget {
+    /// The `#[pyo3_smd(...)]` keys which are valid on this kind of item.
+    fn keys(self) -> &'static [&'static str] {
+        match self {
+            AttrTarget::Container => &[
+                "fmt",
+                "rename",
+                "name",
+                "bound",
+                "dir_fields_only",
+                "transparent",
+            ],
+            AttrTarget::Variant => &["skip", "fmt", "rename", "name"],
+            AttrTarget::Field => &["skip", "include", "fmt", "rename", "name", "lock"],
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            AttrTarget::Container => "the type itself",
+            AttrTarget::Variant => "enum variants",
+            AttrTarget::Field => "fields",
+        }
+    }
+}
+
+/// Format a list of valid choices for an error message: `` `a`, `b`, `c` ``.
+fn one_of(choices: &[&str]) -> String {
+    choices
+        .iter()
+        .map(|choice| format!("`{choice}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The parsed `#[pyo3_smd(...)]` (and legacy) attributes of a type, variant or field.
+///
+/// ```ignore
+/// #[pyo3_smd(skip(Str, Dir), include, fmt = "...", rename = "...", bound = "...")]
+/// ```
+#[derive(Default)]
+pub(crate) struct SmdAttrs {
+    /// Derive macros for which the item is skipped, `All` skips it for every derive macro.
+    skip: Vec<Ident>,
+    /// Force exposure of an otherwise hidden field.
+    pub(crate) include: bool,
+    /// Custom format string.
+    pub(crate) fmt: Option<LitStr>,
+    /// Name to expose instead of the Rust identifier, given with `rename` or its alias `name`.
+    pub(crate) rename: Option<LitStr>,
+    /// The `name` option of PyO3's `#[pyclass(...)]` or `#[pyo3(...)]`.
+    pyo3_name: Option<LitStr>,
+    /// The `module` option of PyO3's `#[pyclass(...)]` or `#[pyo3(...)]`.
+    pyo3_module: Option<LitStr>,
+    /// Whether PyO3 exposes the field with `#[pyo3(get)]`, or every field with `#[pyclass(get_all)]`.
+    pub(crate) pyo3_get: bool,
+    /// Where predicates replacing the inferred trait bounds of generic parameters.
+    pub(crate) bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    /// How to expose a field holding a lock.
+    pub(crate) lock: Option<LockMode>,
+    /// Only list the fields in `__dir__`, instead of adding them to the default listing.
+    pub(crate) dir_fields_only: bool,
+    /// Format a struct with a single field as that field.
+    pub(crate) transparent: bool,
+    /// Keys which were already given, to reject duplicates.
+    seen: Vec<&'static str>,
+}
+
+impl SmdAttrs {
+    pub(crate) fn container(attrs: &[Attribute]) -> syn::Result<Self> {
+        Self::from_attrs(attrs, AttrTarget::Container)
+    }
+
+    pub(crate) fn variant(attrs: &[Attribute]) -> syn::Result<Self> {
+        Self::from_attrs(attrs, AttrTarget::Variant)
+    }
+
+    pub(crate) fn field(attrs: &[Attribute]) -> syn::Result<Self> {
+        Self::from_attrs(attrs, AttrTarget::Field)
+    }
+
+    fn from_attrs(attrs: &[Attribute], target: AttrTarget) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs {
+            let path = attr.path();
+            if path.is_ident(ATTR_NAMESPACE) {
+                attr.parse_nested_meta(|meta| parsed.parse_meta(meta, target))?;
+            } else if path.is_ident(ATTR_SKIP_NAMESPACE) {
+                parsed.check_key("skip", path, target)?;
+                attr.parse_nested_meta(|meta| parsed.push_skip_target(&meta.path))?;
+            } else if path.is_ident(ATTR_NAMESPACE_NO_FMT_SKIP) {
+                attr.meta.require_path_only()?;
+                parsed.check_key("include", path, target)?;
+                parsed.include = true;
+            } else if path.is_ident(ATTR_NAMESPACE_FORMATTER) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("fmt") {
+                        parsed.check_key("fmt", &meta.path, target)?;
+                        parsed.fmt = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error(format!(
+                            "unknown `{ATTR_NAMESPACE_FORMATTER}` attribute, expected `fmt`"
+                        )))
+                    }
+                })?;
+            } else if PYO3_ATTRS.iter().any(|name| path.is_ident(name)) {
+                // Invalid PyO3 options are reported by PyO3 itself
+                let _ = parsed.parse_pyo3_options(attr);
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Read the options of PyO3's attributes that we care about, skipping over all others.
+    ///
+    /// Note that `#[pyclass]` removes these attributes, so they are only visible to derive
+    /// macros listed before it.
+    fn parse_pyo3_options(&mut self, attr: &Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                self.pyo3_name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("module") {
+                self.pyo3_module = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("get") || meta.path.is_ident("get_all") {
+                self.pyo3_get = true;
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(token::Paren) {
+                let _options;
+                parenthesized!(_options in meta.input);
+            }
+            Ok(())
+        })
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta, target: AttrTarget) -> syn::Result<()> {
+        let Some(key) = KEYS.iter().find(|key| meta.path.is_ident(key)) else {
+            return Err(meta.error(format!(
+                "unknown `{ATTR_NAMESPACE}` attribute, expected one of: {}",
+                one_of(target.keys())
+            )));
+        };
+        self.check_key(key, &meta.path, target)?;
+
+        match *key {
+            "skip" => {
+                if meta.input.is_empty() || meta.input.peek(syn::Token![,]) {
+                    // A bare `skip` skips the item for every derive macro
+                    self.skip.push(Ident::new(SKIP_ALL, meta.path.span()));
+                    Ok(())
+                } else {
+                    meta.parse_nested_meta(|inner| self.push_skip_target(&inner.path))
+                }
+            }
+            "include" => {
+                self.include = true;
+                Ok(())
+            }
+            "fmt" => {
+                self.fmt = Some(meta.value()?.parse()?);
+                Ok(())
+            }
+            "rename" | "name" => {
+                if self.rename.is_some() {
+                    return Err(meta.error("`name` is an alias of `rename`, only give one of them"));
+                }
+                self.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            }
+            "lock" => {
+                let mode: LitStr = meta.value()?.parse()?;
+                self.lock = Some(match mode.value().as_str() {
+                    "clone" => LockMode::Clone,
+                    "shared" => LockMode::Shared,
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            mode,
+                            format!(
+                                "unknown lock mode `{other}`, expected one of: `clone`, `shared`"
+                            ),
+                        ))
+                    }
+                });
+                Ok(())
+            }
+            "dir_fields_only" => {
+                self.dir_fields_only = true;
+                Ok(())
+            }
+            "transparent" => {
+                self.transparent = true;
+                Ok(())
+            }
+            "bound" => {
+                let bound: LitStr = meta.value()?.parse()?;
+                self.bound = Some(bound.parse_with(Punctuated::parse_terminated)?);
+                Ok(())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Reject keys which are given twice or which are not valid on `target`.
+    fn check_key(
+        &mut self,
+        key: &'static str,
+        tokens: impl ToTokens,
+        target: AttrTarget,
+    ) -> syn::Result<()> {
+        if !target.keys().contains(&key) {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!(
+                    "`{key}` is not supported on {}, expected one of: {}",
+                    target.description(),
+                    one_of(target.keys())
+                ),
+            ));
+        }
+        if self.seen.contains(&key) {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!("duplicate `{key}` attribute"),
+            ));
+        }
+        self.seen.push(key);
+        Ok(())
+    }
+
+    fn push_skip_target(&mut self, path: &Path) -> syn::Result<()> {
+        let ident = path.require_ident()?;
+        if !SKIP_TARGETS.iter().any(|target| ident == target) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "unknown skip target `{ident}`, expected one of: {}",
+                    one_of(SKIP_TARGETS)
+                ),
+            ));
+        }
+        if self.skip.contains(ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("duplicate skip target `{ident}`"),
+            ));
+        }
+        self.skip.push(ident.clone());
+        Ok(())
+    }
+
+    /// Whether the item is skipped for the derive macro `derive_name`.
+    pub(crate) fn is_skipped(&self, derive_name: &str) -> bool {
+        self.skip
+            .iter()
+            .any(|ident| ident == derive_name || ident == SKIP_ALL)
+    }
+
+    /// The exposed name of the item: the `rename` value or PyO3's `name` if one was given,
+    /// otherwise `default`.
+    pub(crate) fn name_or(&self, default: impl ToString) -> String {
+        match self.rename.as_ref().or(self.pyo3_name.as_ref()) {
+            Some(name) => name.value(),
+            None => default.to_string(),
+        }
+    }
+
+    /// An expression of the Python-visible name of the type, preceded by its module if `qualified`.
+    ///
+    /// For pyclasses the name and module registered by PyO3 are used, as `#[pyclass(...)]` is
+    /// not visible to derive macros listed after it.
+    pub(crate) fn type_name(&self, ident: &Ident, pyclass: bool, qualified: bool) -> TokenStream {
+        let name = match &self.rename {
+            Some(rename) => quote! { #rename },
+            None if pyclass => quote! { <Self as pyo3::PyTypeInfo>::NAME },
+            None => {
+                let name = self.name_or(ident);
+                quote! { #name }
+            }
+        };
+        let module = match &self.pyo3_module {
+            _ if !qualified => return name,
+            Some(module) => quote! { Some(#module) },
+            None if pyclass => quote! { <Self as pyo3::PyTypeInfo>::MODULE },
+            None => return name,
+        };
+        quote! {
+            match #module {
+                Some(module) => format!("{}.{}", module, #name),
+                None => #name.to_string(),
+            }
+        }
+    }
+}
+
+/// The attributes of the item, its variants and its fields, along with their kind.
+fn all_attrs(input: &DeriveInput) -> Vec<(&[Attribute], AttrTarget)> {
+    let mut attrs = vec![(input.attrs.as_slice(), AttrTarget::Container)];
+    match &input.data {
+        Data::Struct(data) => attrs.extend(
+            data.fields
+                .iter()
+                .map(|f| (f.attrs.as_slice(), AttrTarget::Field)),
+        ),
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                attrs.push((&variant.attrs, AttrTarget::Variant));
+                attrs.extend(
+                    variant
+                        .fields
+                        .iter()
+                        .map(|f| (f.attrs.as_slice(), AttrTarget::Field)),
+                );
+            }
+        }
+        Data::Union(_) => {}
+    }
+    attrs
+}
+
+/// Parse every attribute of the item up front, so that all errors are reported
+/// regardless of which fields or variants a derive macro ends up looking at.
+pub(crate) fn validate_attrs(input: &DeriveInput) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    for (attrs, target) in all_attrs(input) {
+        if let Err(e) = SmdAttrs::from_attrs(attrs, target) {
+            match &mut errors {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Emit a deprecation warning for every legacy attribute (`#[skip(...)]`, `#[pyo3_fmt_no_skip]`
+/// and `#[format(...)]`) on the item, its variants or its fields.
+///
+/// Every derive on the item emits these, as a derive macro only sees the attributes listed after
+/// its own `#[derive(...)]` and so cannot tell which of the other derives run. The warnings are
+/// identical, so rustc shows each of them about once and counts the rest as duplicates.
+pub(crate) fn deprecation_warnings(input: &DeriveInput) -> TokenStream {
+    all_attrs(input)
+        .into_iter()
+        .flat_map(|(attrs, _)| attrs)
+        .filter_map(|attr| {
+            let path = attr.path();
+            let note = if path.is_ident(ATTR_SKIP_NAMESPACE) {
+                "`#[skip(...)]` is deprecated, use `#[pyo3_smd(skip(...))]` instead"
+            } else if path.is_ident(ATTR_NAMESPACE_NO_FMT_SKIP) {
+                "`#[pyo3_fmt_no_skip]` is deprecated, use `#[pyo3_smd(include)]` instead"
+            } else if path.is_ident(ATTR_NAMESPACE_FORMATTER) {
+                "`#[format(...)]` is deprecated, use `#[pyo3_smd(fmt = \"...\")]` instead"
+            } else {
+                return None;
+            };
+            Some(quote_spanned! {path.span()=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    const fn legacy_attribute() {}
+                    legacy_attribute()
+                };
+            })
+        })
+        .collect()
+}
+
+/// Emit a warning when the derive macro comes after `#[pyclass]`, as PyO3 has then already
+/// expanded and removed it along with the `#[pyo3(get, name = "...")]` field options.
+pub(crate) fn pyclass_order_warning(input: &DeriveInput, macro_name: &str) -> TokenStream {
+    let is_pyclass = |attr: &Attribute| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "pyclass")
+    };
+    if input.attrs.iter().any(is_pyclass) {
+        return TokenStream::new();
+    }
+    let note = format!(
+        "list `#[derive({macro_name})]` before `#[pyclass]`, which otherwise removes the \
+         `#[pyo3(get, name = \"...\")]` field options before they are read"
+    );
+    quote_spanned! {input.ident.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            const fn derive_after_pyclass() {}
+            derive_after_pyclass()
+        };
+    }
+}
@@ -0,0 +1,63 @@
+use crate::attrs::SmdAttrs;
+use proc_macro2::TokenStream;
+use syn::visit::Visit;
+use syn::{parse_quote, Generics, Ident, Type, TypePath};
+
+/// Collects the type parameters which appear in a type.
+struct TypeParamVisitor<'a> {
+    params: Vec<&'a Ident>,
+    used: Vec<&'a Ident>,
+}
+
+impl<'ast> Visit<'ast> for TypeParamVisitor<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        // `T`, `T::Assoc` or `<T as Trait>::Assoc` all depend on `T`
+        if ty.path.leading_colon.is_none() {
+            if let Some(first) = ty.path.segments.first() {
+                if let Some(param) = self.params.iter().find(|param| **param == &first.ident) {
+                    if !self.used.contains(param) {
+                        self.used.push(param);
+                    }
+                }
+            }
+        }
+        syn::visit::visit_type_path(self, ty);
+    }
+}
+
+/// The generics of an impl block for the derived type.
+///
+/// If the type has a `#[pyo3_smd(bound = "...")]` attribute, its predicates are added to the where
+/// clause. Otherwise every type parameter appearing in one of `field_types` must implement `bound`.
+pub(crate) fn impl_generics(
+    generics: &Generics,
+    container: &SmdAttrs,
+    field_types: &[&Type],
+    bound: Option<TokenStream>,
+) -> Generics {
+    let mut impl_generics = generics.clone();
+    if let Some(predicates) = &container.bound {
+        impl_generics
+            .make_where_clause()
+            .predicates
+            .extend(predicates.iter().cloned());
+    } else if let Some(bound) = bound {
+        let mut visitor = TypeParamVisitor {
+            params: generics.type_params().map(|param| &param.ident).collect(),
+            used: Vec::new(),
+        };
+        for ty in field_types {
+            visitor.visit_type(ty);
+        }
+        // Keep the declaration order of the parameters for a stable output
+        let used = visitor
+            .params
+            .into_iter()
+            .filter(|param| visitor.used.contains(param));
+        let predicates = impl_generics.make_where_clause();
+        for param in used {
+            predicates.predicates.push(parse_quote!(#param: #bound));
+        }
+    }
+    impl_generics
+}
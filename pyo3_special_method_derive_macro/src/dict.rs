@@ -0,0 +1,81 @@
+use crate::attrs::{validate_attrs, SmdAttrs};
+use crate::bounds::impl_generics;
+use crate::fields::{struct_fields, variant_fields, variant_pattern};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+const DERIVE_NAME: &str = "Dict";
+
+pub(crate) fn impl_dict(input: &DeriveInput) -> syn::Result<TokenStream> {
+    validate_attrs(input)?;
+
+    let name = &input.ident;
+    let container = SmdAttrs::container(&input.attrs)?;
+    let generics = impl_generics(&input.generics, &container, &[], None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            // Insert each field, tuple fields are named `_0`, `_1`, ...
+            let inserter = struct_fields(data, &container, DERIVE_NAME)?
+                .iter()
+                .map(|field| {
+                    let member = field.member();
+                    let field_name = field.name();
+                    let value = field.to_python(quote! { self.#member });
+                    quote! { values.insert(#field_name.to_string(), #value?); }
+                })
+                .collect::<Vec<_>>();
+            quote! { #(#inserter)* }
+        }
+        Data::Enum(data_enum) => {
+            let mut match_arms = Vec::new();
+            for variant in &data_enum.variants {
+                // If a variant was skipped just output no __dict__ data.
+                let fields = variant_fields(variant, DERIVE_NAME)?;
+                let inserter = fields.iter().map(|field| {
+                    let binding = field.binding();
+                    let field_name = field.name();
+                    let value = field.to_python(quote! { #binding });
+                    quote! { values.insert(#field_name.to_string(), #value?); }
+                });
+                let bound = fields.iter().map(|field| field.index).collect::<Vec<_>>();
+                let pattern = variant_pattern(variant, &bound);
+                match_arms.push(quote! {
+                    #pattern => {
+                        #(#inserter)*
+                    }
+                });
+            }
+            quote! {
+                match self {
+                    #(#match_arms)*
+                }
+            }
+        }
+        Data::Union(u) => {
+            return Err(syn::Error::new_spanned(
+                u.union_token,
+                "Unions are not supported for Dict derive",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[pyo3::pymethods]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[allow(non_snake_case)]
+            #[getter]
+            pub fn __dict__(&self) -> pyo3::PyResult<std::collections::HashMap<String, pyo3::Py<pyo3::PyAny>>> {
+                #[allow(unused_imports)]
+                use pyo3::IntoPy;
+
+                #[allow(unused_mut)]
+                let mut values = std::collections::HashMap::new();
+                #body
+                Ok(values)
+            }
+        }
+    })
+}
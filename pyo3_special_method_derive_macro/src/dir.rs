@@ -0,0 +1,111 @@
+use crate::attrs::{validate_attrs, SmdAttrs};
+use crate::bounds::impl_generics;
+use crate::fields::{struct_fields, variant_fields, variant_pattern, ExposedField};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+const DERIVE_NAME: &str = "Dir";
+
+pub(crate) fn impl_dir(input: &DeriveInput) -> syn::Result<TokenStream> {
+    validate_attrs(input)?;
+
+    // Get the name of the struct
+    let name = &input.ident;
+    let container = SmdAttrs::container(&input.attrs)?;
+    let generics = impl_generics(&input.generics, &container, &[], None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Enums also list their variants at the class level
+    let mut variants = None;
+    // The merged listing needs the Python object, which the variant is then borrowed from
+    let receiver = if container.dir_fields_only {
+        quote! { self }
+    } else {
+        quote! { &*slf.try_borrow()? }
+    };
+
+    // Generate code to match the struct's fields, or the fields of the active variant
+    let body = match &input.data {
+        Data::Struct(data) => {
+            // Extract the names of the fields, tuple fields are named `_0`, `_1`, ...
+            let field_names = struct_fields(data, &container, DERIVE_NAME)?
+                .iter()
+                .map(ExposedField::name)
+                .collect::<Vec<_>>();
+            quote! { vec![#(#field_names.to_string()),*] }
+        }
+        Data::Enum(e) => {
+            let mut arms = Vec::new();
+            let mut variant_names = Vec::new();
+            for variant in &e.variants {
+                let variant_attrs = SmdAttrs::variant(&variant.attrs)?;
+                if !variant_attrs.is_skipped(DERIVE_NAME) {
+                    variant_names.push(variant_attrs.name_or(&variant.ident));
+                }
+                let field_names = variant_fields(variant, DERIVE_NAME)?
+                    .iter()
+                    .map(ExposedField::name)
+                    .collect::<Vec<_>>();
+                let pattern = variant_pattern(variant, &[]);
+                arms.push(quote! { #pattern => vec![#(#field_names.to_string()),*], });
+            }
+            variants = Some(quote! {
+                /// The names of the variants of the enum.
+                #[staticmethod]
+                pub fn variants() -> Vec<String> {
+                    vec![#(#variant_names.to_string()),*]
+                }
+            });
+            quote! {
+                match #receiver {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(u) => {
+            return Err(syn::Error::new_spanned(
+                u.union_token,
+                "Unions are not supported for Dir derive",
+            ))
+        }
+    };
+
+    // By default the fields are added to what `object.__dir__` lists for the instance, ie. the
+    // attributes of its class, of the subclass of its variant and of its own `__dict__`, so that
+    // methods, getters and dunder methods remain visible
+    let dir = if container.dir_fields_only {
+        quote! {
+            pub fn __dir__(&self) -> Vec<String> {
+                #body
+            }
+        }
+    } else {
+        quote! {
+            pub fn __dir__(slf: &pyo3::Bound<'_, Self>) -> pyo3::PyResult<Vec<String>> {
+                use pyo3::types::PyAnyMethods;
+                let fields: Vec<String> = #body;
+                let mut names = slf
+                    .py()
+                    .get_type::<pyo3::PyAny>()
+                    .call_method1("__dir__", (slf,))?
+                    .extract::<Vec<String>>()?;
+                for field in fields {
+                    if !names.contains(&field) {
+                        names.push(field);
+                    }
+                }
+                Ok(names)
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[pyo3::pymethods]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #dir
+
+            #variants
+        }
+    })
+}
@@ -0,0 +1,144 @@
+use crate::attrs::{LockMode, SmdAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DataStruct, Field, Fields, Ident, Member, Variant, Visibility};
+
+/// A field which a derive macro exposes, along with its position and attributes.
+pub(crate) struct ExposedField<'a> {
+    pub(crate) index: usize,
+    pub(crate) field: &'a Field,
+    pub(crate) attrs: SmdAttrs,
+}
+
+impl ExposedField<'_> {
+    /// See [`field_binding`].
+    pub(crate) fn binding(&self) -> Ident {
+        field_binding(self.field, self.index)
+    }
+
+    /// See [`field_member`].
+    pub(crate) fn member(&self) -> Member {
+        field_member(self.field, self.index)
+    }
+
+    /// The Python-visible name of the field.
+    pub(crate) fn name(&self) -> String {
+        self.attrs.name_or(self.binding())
+    }
+
+    /// An expression converting the field `value` to a `PyResult<Py<PyAny>>`, going through
+    /// its lock if the field has a `lock` mode.
+    pub(crate) fn to_python(&self, value: TokenStream) -> TokenStream {
+        let name = self.name();
+        let convert = match self.attrs.lock {
+            None => {
+                return quote! { pyo3::PyResult::Ok(pyo3::Python::with_gil(|py| #value.clone().into_py(py))) }
+            }
+            Some(LockMode::Clone) => quote! { |value| value.clone().into_py(py) },
+            Some(LockMode::Shared) => quote! { |value| pyo3::Py::clone_ref(value, py).into_any() },
+        };
+        quote! {
+            pyo3::Python::with_gil(|py| {
+                use pyo3_special_method_derive::PyLock;
+                #value.try_with(#name, #convert)
+            })
+        }
+    }
+}
+
+/// The fields of a struct which the derive macro `derive_name` exposes.
+///
+/// Every derive macro resolves fields through here, so that all views of an object agree:
+/// - a field skipped for the derive macro (or all of them) is never exposed
+/// - a `pub` field is exposed, restricted visibility such as `pub(crate)` counts as private
+/// - a private field is exposed if it is included with `#[pyo3_smd(include)]`, or if PyO3 exposes
+///   it with `#[pyo3(get)]` or `#[pyclass(get_all)]`
+pub(crate) fn struct_fields<'a>(
+    data: &'a DataStruct,
+    container: &SmdAttrs,
+    derive_name: &str,
+) -> syn::Result<Vec<ExposedField<'a>>> {
+    let mut fields = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let attrs = SmdAttrs::field(&field.attrs)?;
+        let is_visible = matches!(field.vis, Visibility::Public(_))
+            || attrs.include
+            || attrs.pyo3_get
+            || container.pyo3_get;
+        if is_visible && !attrs.is_skipped(derive_name) {
+            fields.push(ExposedField {
+                index,
+                field,
+                attrs,
+            });
+        }
+    }
+    Ok(fields)
+}
+
+/// The fields of an enum variant which the derive macro `derive_name` exposes: those which are
+/// not skipped, as they are as visible as the enum itself. A skipped variant exposes no fields.
+pub(crate) fn variant_fields<'a>(
+    variant: &'a Variant,
+    derive_name: &str,
+) -> syn::Result<Vec<ExposedField<'a>>> {
+    let mut fields = Vec::new();
+    if SmdAttrs::variant(&variant.attrs)?.is_skipped(derive_name) {
+        return Ok(fields);
+    }
+    for (index, field) in variant.fields.iter().enumerate() {
+        let attrs = SmdAttrs::field(&field.attrs)?;
+        if !attrs.is_skipped(derive_name) {
+            fields.push(ExposedField {
+                index,
+                field,
+                attrs,
+            });
+        }
+    }
+    Ok(fields)
+}
+
+/// The name a field is bound to in a match pattern: its identifier, or `_0`, `_1`, ... for
+/// tuple fields. This is also the default attribute name, just like PyO3 exposes tuple variants.
+pub(crate) fn field_binding(field: &Field, index: usize) -> Ident {
+    match &field.ident {
+        Some(ident) => ident.clone(),
+        None => format_ident!("_{}", index),
+    }
+}
+
+/// The member to access a struct field with: `self.name` or `self.0`.
+pub(crate) fn field_member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(index.into()),
+    }
+}
+
+/// A pattern matching `Self::#variant` which binds the fields at the `bound` indices with
+/// [`field_binding`] and ignores the others.
+pub(crate) fn variant_pattern(variant: &Variant, bound: &[usize]) -> TokenStream {
+    let ident = &variant.ident;
+    let bindings = variant.fields.iter().enumerate();
+    match &variant.fields {
+        Fields::Named(_) => {
+            let bindings = bindings
+                .filter(|(i, _)| bound.contains(i))
+                .map(|(_, field)| &field.ident);
+            quote! { Self::#ident { #(#bindings,)* .. } }
+        }
+        Fields::Unnamed(_) => {
+            let bindings = bindings.map(|(i, field)| {
+                if bound.contains(&i) {
+                    let binding = field_binding(field, i);
+                    quote! { #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            quote! { Self::#ident(#(#bindings),*) }
+        }
+        Fields::Unit => quote! { Self::#ident },
+    }
+}
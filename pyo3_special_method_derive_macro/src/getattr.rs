@@ -0,0 +1,89 @@
+use crate::attrs::{validate_attrs, SmdAttrs};
+use crate::bounds::impl_generics;
+use crate::fields::{struct_fields, variant_fields, variant_pattern};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+const DERIVE_NAME: &str = "Getattr";
+
+pub(crate) fn impl_getattr(input: &DeriveInput) -> syn::Result<TokenStream> {
+    validate_attrs(input)?;
+
+    let name = &input.ident;
+    let container = SmdAttrs::container(&input.attrs)?;
+    let type_name = container.type_name(name, true, false);
+    let generics = impl_generics(&input.generics, &container, &[], None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            // Match on the names of the fields, tuple fields are named `_0`, `_1`, ...
+            let matchers = struct_fields(data, &container, DERIVE_NAME)?
+                .iter()
+                .map(|field| {
+                    let member = field.member();
+                    let field_name = field.name();
+                    let value = field.to_python(quote! { self.#member });
+                    quote! { #field_name => #value, }
+                })
+                .collect::<Vec<_>>();
+
+            quote! {
+                match attr.as_str() {
+                    #(#matchers)*
+                    _ => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}' has no attribute '{attr}'", #type_name)))
+                }
+            }
+        }
+        Data::Enum(data_enum) => {
+            let mut match_arms = Vec::new();
+            for variant in &data_enum.variants {
+                let variant_attrs = SmdAttrs::variant(&variant.attrs)?;
+                let variant_name = variant_attrs.name_or(&variant.ident);
+                // If a variant was skipped always raise an exception
+                let fields = variant_fields(variant, DERIVE_NAME)?;
+                let matchers = fields.iter().map(|field| {
+                    let binding = field.binding();
+                    let field_name = field.name();
+                    let value = field.to_python(quote! { #binding });
+                    quote! { #field_name => #value, }
+                });
+                let bound = fields.iter().map(|field| field.index).collect::<Vec<_>>();
+                let pattern = variant_pattern(variant, &bound);
+                match_arms.push(quote! {
+                    #pattern => {
+                        match attr.as_str() {
+                            #(#matchers)*
+                            _ => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}.{}' has no attribute '{attr}'", #type_name, #variant_name)))
+                        }
+                    }
+                });
+            }
+            quote! {
+                match self {
+                    #(#match_arms)*
+                }
+            }
+        }
+        Data::Union(u) => {
+            return Err(syn::Error::new_spanned(
+                u.union_token,
+                "Unions are not supported for Getattr derive",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[pyo3::pymethods]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[allow(non_snake_case)]
+            pub fn __getattr__(&self, attr: String) -> pyo3::PyResult<pyo3::Py<pyo3::PyAny>> {
+                #[allow(unused_imports)]
+                use pyo3::IntoPy;
+
+                #body
+            }
+        }
+    })
+}
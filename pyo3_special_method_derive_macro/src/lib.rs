@@ -1,16 +1,21 @@
 extern crate proc_macro;
+use attrs::{deprecation_warnings, pyclass_order_warning};
+use dict::impl_dict;
+use dir::impl_dir;
+use getattr::impl_getattr;
 use proc_macro::TokenStream;
 use quote::quote;
 use str_repr::{impl_formatter, DeriveType};
-use syn::{parse::Parser, parse_macro_input, Data, DeriveInput, Fields, Ident, Visibility};
-
+use syn::{parse::Parser, parse_macro_input, parse_quote, DeriveInput, Ident};
+
+mod attrs;
+mod bounds;
+mod dict;
+mod dir;
+mod fields;
+mod getattr;
 mod str_repr;
 
-const ATTR_SKIP_NAMESPACE: &str = "skip";
-const ATTR_NAMESPACE_NO_FMT_SKIP: &str = "pyo3_fmt_no_skip";
-const ATTR_NAMESPACE_FORMATTER: &str = "format";
-const SKIP_ALL: &str = "All";
-
 fn implements_debug(ty: &Ident) -> bool {
     let expanded = quote! {
         fn _check_impl<T: std::fmt::Debug>() {}
@@ -35,16 +40,28 @@ fn implements_display(ty: &Ident) -> bool {
 
 /// Add a `__dir__` method to a struct or enum.
 ///
-/// - Skip exposure of certain fields by adding `Dir` to the `#[skip(...)]` attribute macro: `#[skip(Dir)]`
-/// - For structs, all fields are skipped which are not marked `pub`
-/// - Skip exposure of certain fields for all derive macros by adding `All`: `#[skip(All)]`
+/// The exposed fields are added to the default listing of `object.__dir__` for the instance, so that methods, getters,
+/// dunder methods and the attributes of the instance remain visible to IDEs and `help()`. This `__dir__` takes the
+/// instance as a `&Bound<'_, Self>` and returns a `PyResult`. Attributes which PyO3 itself exposes, such as `#[pyo3(get)]`
+/// fields or the fields of a complex enum variant, are listed even if they are skipped. Only list the exposed fields,
+/// with a `__dir__(&self)` method, with `#[pyo3_smd(dir_fields_only)]` on the type.
+///
+/// For enums, an instance lists the fields of its active variant. The names of the variants which are not skipped
+/// are available on the class with the `variants()` static method.
+///
+/// - Skip exposure of certain fields by adding `Dir` to the `#[pyo3_smd(skip(...))]` attribute macro: `#[pyo3_smd(skip(Dir))]`
+/// - Force exposure of a field which is not `pub` with `#[pyo3_smd(include)]`
+/// - For structs, all fields are skipped which are not marked `pub` (restricted visibilities such as `pub(crate)` or `pub(super)` count as private)
+/// - Skip exposure of certain fields for all derive macros with `#[pyo3_smd(skip)]`
+/// - Expose a field under another name with `#[pyo3_smd(rename = "...")]` or `#[pyo3_smd(name = "...")]`
+/// - Fields of tuple structs and tuple variants are named `_0`, `_1`, ... like PyO3 does
 ///
 /// ## Example
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Dir;
-/// #[pyclass]
 /// #[derive(Dir)]
+/// #[pyclass]
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
@@ -52,194 +69,21 @@ fn implements_display(ty: &Ident) -> bool {
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(Dir, attributes(skip))]
+#[proc_macro_derive(Dir, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn dir_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    // Get the name of the struct
-    let name = &input.ident;
-
-    // Generate code to match the struct's fields
-    let expanded = match input.data {
-        Data::Struct(data) => {
-            match data.fields {
-                Fields::Named(fields) => {
-                    // If the struct has named fields extract their names
-                    let field_names = fields
-                        .named
-                        .iter()
-                        .filter(|f| {
-                            !f.attrs.iter().any(|attr| {
-                                let mut is_skip = false;
-                                if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                                    // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                                    attr.parse_nested_meta(|meta| {
-                                        is_skip |= meta.path.is_ident("Dir")
-                                            || meta.path.is_ident(SKIP_ALL);
-                                        Ok(())
-                                    })
-                                    .unwrap();
-                                }
-                                is_skip
-                            })
-                        })
-                        .filter(|f| matches!(f.vis, Visibility::Public(_)))
-                        .map(|f| f.ident.as_ref().unwrap())
-                        .collect::<Vec<_>>();
-
-                    if field_names.is_empty() {
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                pub fn __dir__(&self) -> Vec<String> {
-                                    Vec::new()
-                                }
-                            }
-                        }
-                    } else {
-                        // Prepare an array where the elements are expressions that prepare the field vec
-                        let mut assigner = proc_macro2::TokenStream::new();
-                        quote_into::quote_into!(assigner += [#{
-                            for name in field_names {
-                                quote_into::quote_into!(assigner += (names.push(stringify!(#name).to_string())),)
-                            }
-                        }];);
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                pub fn __dir__(&self) -> Vec<String> {
-                                    let mut names = Vec::new();
-                                    #assigner
-                                    names
-                                }
-                            }
-                        }
-                    }
-                }
-                Fields::Unit => {
-                    // If the struct has no fields
-                    quote! {
-                        #[pyo3::pymethods]
-                        impl #name {
-                            pub fn __dir__(&self) -> Vec<String> {
-                                Vec::new()
-                            }
-                        }
-                    }
-                }
-                Fields::Unnamed(_) => {
-                    quote! {
-                        compile_error!("Unnamed fields for struct are not supported for Dir derive.");
-                    }
-                }
-            }
-        }
-        Data::Enum(e) => {
-            let matchers = e.variants.iter()
-                .filter(|variant| {
-                    !variant.attrs.iter().any(|attr| {
-                        let mut is_skip = false;
-                        if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                            // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                            attr.parse_nested_meta(|meta| {
-                                is_skip |= meta.path.is_ident("Dir") || meta.path.is_ident(SKIP_ALL);
-                                Ok(())
-                            })
-                            .unwrap();
-                        }
-                        is_skip
-                    })
-                })
-                .map(|variant| {
-                    let ident = &variant.ident;
-                    match &variant.fields {
-                        Fields::Unit => {
-                            quote! {
-                                Self::#ident => { vec![] }
-                            }
-                        }
-                        Fields::Unnamed(_) => {
-                            unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                        }
-                        Fields::Named(fields) => {
-                            let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap().clone()).collect::<Vec<_>>();
-
-                            if field_names.is_empty() {
-                                quote! { Self::#ident { .. } => { vec![] } }
-                            } else {
-                                let mut assigner = proc_macro2::TokenStream::new();
-                                quote_into::quote_into!(assigner += [#{
-                                    for name in &field_names {
-                                        quote_into::quote_into!(assigner += (names.push(stringify!(#name).to_string())),)
-                                    }
-                                }];);
-
-                                quote! {
-                                    Self::#ident { .. } => {
-                                        let mut names = Vec::new();
-                                        #assigner
-                                        names
-                                    }
-                                }
-                            }
-                        }
-                    }
-                });
-            let skipped_matchers = e
-                .variants
-                .iter()
-                .filter(|variant| {
-                    variant.attrs.iter().any(|attr| {
-                        let mut is_skip = false;
-                        if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                            // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                            attr.parse_nested_meta(|meta| {
-                                is_skip |=
-                                    meta.path.is_ident("Dir") || meta.path.is_ident(SKIP_ALL);
-                                Ok(())
-                            })
-                            .unwrap();
-                        };
-                        is_skip
-                    })
-                })
-                .map(|variant| {
-                    let ident = &variant.ident;
-                    match &variant.fields {
-                        Fields::Unit => {
-                            quote! {
-                                Self::#ident => { vec![] }
-                            }
-                        }
-                        Fields::Unnamed(_) => {
-                            unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                        }
-                        Fields::Named(_) => {
-                            quote! {
-                                Self::#ident { .. } => { vec![] }
-                            }
-                        }
-                    }
-                });
-            quote! {
-                #[pyo3::pymethods]
-                impl #name {
-                    pub fn __dir__(&self) -> Vec<String> {
-                        match self {
-                            #(#matchers)*
-                            #(#skipped_matchers)*
-                        }
-                    }
-                }
-            }
-        }
-        Data::Union(_) => {
-            quote! {
-                compile_error!("Unions are not supported for Dir derive");
-            }
-        }
+    let expanded = match impl_dir(&input) {
+        Ok(x) => x,
+        Err(e) => e.into_compile_error(),
     };
-    TokenStream::from(expanded)
+    let mut warnings = deprecation_warnings(&input);
+    warnings.extend(pyclass_order_warning(&input, "Dir"));
+
+    TokenStream::from(quote! {
+        #expanded
+        #warnings
+    })
 }
 
 /// Add a `__str__` method to the struct or enum.
@@ -248,48 +92,54 @@ pub fn dir_derive(input: TokenStream) -> TokenStream {
 /// Certain implementations are automatically provided, but you can implement the required trait yourself
 /// or use a provided convenience macro.
 ///
-/// - Skip exposure of certain fields by adding `Str` to the `#[skip(...)]` attribute macro: `#[skip(Str)]`
-/// - For structs, all fields are skipped which are not marked `pub`
-/// - Skip exposure of certain fields for all derive macros by adding `All`: `#[skip(All)]`
-///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and variant are formatted.
+/// - Skip exposure of certain fields by adding `Str` to the `#[pyo3_smd(skip(...))]` attribute macro: `#[pyo3_smd(skip(Str))]`
+/// - For structs, all fields are skipped which are not marked `pub` (restricted visibilities such as `pub(crate)` or `pub(super)` count as private)
+/// - Skip exposure of certain fields for all derive macros with `#[pyo3_smd(skip)]`
+/// - Force exposure of a field which is not `pub` with `#[pyo3_smd(include)]`
+/// - Expose a field, variant or the type under another name with `#[pyo3_smd(rename = "...")]`
+/// - On generic types, each type parameter used by a displayed field is bounded by the trait,
+///   override these bounds with `#[pyo3_smd(bound = "T: ...")]` on the type
+/// - Display a struct with a single field as that field with `#[pyo3_smd(transparent)]` on the type
+/// - Fields of tuple structs and tuple variants are displayed by position, eg. `Point(1.0, 2.0)`
+///
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and variant are formatted.
 /// By default it is `{}.{}`. The format string takes 2 (filled in as name, variant), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Str;
-/// #[pyclass]
 /// #[derive(Str)]
-/// #[format(fmt = "{}.{}")]
+/// #[pyclass]
+/// #[pyo3_smd(fmt = "{}.{}")]
 /// enum Person {
 ///     Alive,
 ///     Dead
 /// }
 /// ```
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and fields are formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and fields are formatted.
 /// By default it is `{}({})`. The format string takes 2 (filled in as name, fields), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Str;
-/// #[pyclass]
 /// #[derive(Str)]
-/// #[format(fmt = "{}({})")]
+/// #[pyclass]
+/// #[pyo3_smd(fmt = "{}({})")]
 /// struct Mountain {
 ///     pub height: usize,
 /// }
 /// ```
 ///
-/// - A struct field may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
-/// - An enum variant may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
+/// - A struct field may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
+/// - An enum variant may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
 ///
 /// ## Example
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Str;
-/// #[pyclass]
 /// #[derive(Str)]
+/// #[pyclass]
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
@@ -297,26 +147,32 @@ pub fn dir_derive(input: TokenStream) -> TokenStream {
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(Str, attributes(skip, pyo3_fmt_no_skip, format))]
+#[proc_macro_derive(Str, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn str_derive(input_stream: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input_stream as DeriveInput);
 
     // Get the name of the struct
     let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let display_derive_body = match impl_formatter(&input, DeriveType::ForAutoDisplay, "Str") {
+    let display_derive_body = match impl_formatter(&input, DeriveType::ForAutoDisplay, "Str", true)
+    {
         Ok(x) => x,
-        Err(e) => e.into_compile_error(),
+        Err(e) => return e.into_compile_error().into(),
     };
 
+    let mut warnings = deprecation_warnings(&input);
+    warnings.extend(pyclass_order_warning(&input, "Str"));
+
     let expanded = quote! {
         #display_derive_body
+        #warnings
 
         #[pyo3::pymethods]
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
             pub fn __str__(&self) -> String {
                 use pyo3_special_method_derive::PyDisplay;
-                self.fmt_display()
+                self.fmt_str()
             }
         }
     };
@@ -328,37 +184,37 @@ pub fn str_derive(input_stream: TokenStream) -> TokenStream {
 ///
 /// This has the same requirements and behavior of [`Str`].
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and variant are formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and variant are formatted.
 /// By default it is `{}.{}`. The format string takes 2 (filled in as name, variant), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3_special_method_derive::AutoDisplay;
 /// #[derive(AutoDisplay)]
-/// #[format(fmt = "{}.{}")]
+/// #[pyo3_smd(fmt = "{}.{}")]
 /// enum Person {
 ///     Alive,
 ///     Dead
 /// }
 /// ```
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and fields are formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and fields are formatted.
 /// By default it is `{}({})`. The format string takes 2 (filled in as name, fields), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3_special_method_derive::AutoDisplay;
 /// #[derive(AutoDisplay)]
-/// #[format(fmt = "{}({})")]
+/// #[pyo3_smd(fmt = "{}({})")]
 /// struct Mountain {
 ///     pub height: usize,
 /// }
 /// ```
 ///
-/// - A struct field may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
-/// - An enum variant may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
+/// - A struct field may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
+/// - An enum variant may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
 ///
 /// ## Example
 ///
-/// The `formatter` also has other uses, outlined below:
+/// The `pyo3_smd` attribute also has other uses, outlined below:
 ///
 /// ```ignore
 /// use pyo3_special_method_derive::AutoDisplay;
@@ -366,35 +222,48 @@ pub fn str_derive(input_stream: TokenStream) -> TokenStream {
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
-///     #[format(skip)]
+///     #[pyo3_smd(skip)]
 ///     pub phone_number: String,
-///     #[format] // -> force display of private field
+///     #[pyo3_smd(include)] // -> force display of private field
 ///     hash: u32,
 /// }
 /// ```
-#[proc_macro_derive(AutoDisplay, attributes(skip, pyo3_fmt_no_skip, format))]
+#[proc_macro_derive(AutoDisplay, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn auto_display(input_stream: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input_stream as DeriveInput);
     let name = &input.ident;
 
     let display_debug_derive_body =
-        impl_formatter(&input, DeriveType::ForAutoDisplay, "AutoDisplay");
+        impl_formatter(&input, DeriveType::ForAutoDisplay, "AutoDisplay", false);
 
     let display_debug_derive_body = match display_debug_derive_body {
         Ok(x) => x,
-        Err(e) => e.into_compile_error(),
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let warnings = deprecation_warnings(&input);
+
+    let display_debug_derive_body = quote! {
+        #display_debug_derive_body
+        #warnings
     };
 
     if implements_display(name) {
         TokenStream::from(display_debug_derive_body)
     } else {
+        // Display is available whenever PyDisplay is
+        let mut generics = input.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(Self: pyo3_special_method_derive::PyDisplay));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let expanded = quote! {
             #display_debug_derive_body
 
-            impl std::fmt::Display for #name {
+            impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     use pyo3_special_method_derive::PyDisplay;
-                    write!(f, "{}", self.fmt_display())
+                    self.fmt_str_into(f)
                 }
             }
         };
@@ -409,46 +278,52 @@ pub fn auto_display(input_stream: TokenStream) -> TokenStream {
 /// Certain implementations are automatically provided, but you can implement the required trait yourself
 /// or use a provided convenience macro.
 ///
-/// - Skip exposure of certain fields by adding `Repr` to the `#[skip(...)]` attribute macro: `#[skip(Repr)]`
-/// - For structs, all fields are skipped which are not marked `pub`
-/// - Skip exposure of certain fields for all derive macros by adding `All`: `#[skip(All)]`
-///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and variant are formatted.
+/// - Skip exposure of certain fields by adding `Repr` to the `#[pyo3_smd(skip(...))]` attribute macro: `#[pyo3_smd(skip(Repr))]`
+/// - For structs, all fields are skipped which are not marked `pub` (restricted visibilities such as `pub(crate)` or `pub(super)` count as private)
+/// - Skip exposure of certain fields for all derive macros with `#[pyo3_smd(skip)]`
+/// - Force exposure of a field which is not `pub` with `#[pyo3_smd(include)]`
+/// - Expose a field, variant or the type under another name with `#[pyo3_smd(rename = "...")]`
+/// - On generic types, each type parameter used by a displayed field is bounded by the trait,
+///   override these bounds with `#[pyo3_smd(bound = "T: ...")]` on the type
+/// - Display a struct with a single field as that field with `#[pyo3_smd(transparent)]` on the type
+/// - Fields of tuple structs and tuple variants are displayed by position, eg. `Point(1.0, 2.0)`
+///
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and variant are formatted.
 /// By default it is `{}.{}`. The format string takes 2 (filled in as name, variant), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Repr;
 /// #[pyclass]
-/// #[format(fmt = "{}.{}")]
+/// #[pyo3_smd(fmt = "{}.{}")]
 /// enum Person {
 ///     Alive,
 ///     Dead
 /// }
 /// ```
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and fields are formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and fields are formatted.
 /// By default it is `{}({})`. The format string takes 2 (filled in as name, fields), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Repr;
 /// #[pyclass]
-/// #[format(fmt = "{}({})")]
+/// #[pyo3_smd(fmt = "{}({})")]
 /// struct Mountain {
 ///     pub height: usize,
 /// }
 /// ```
 ///
-/// - A struct field may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
-/// - An enum variant may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
+/// - A struct field may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
+/// - An enum variant may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
 ///
 /// ## Example
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Repr;
-/// #[pyclass]
 /// #[derive(Repr)]
+/// #[pyclass]
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
@@ -456,25 +331,30 @@ pub fn auto_display(input_stream: TokenStream) -> TokenStream {
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(Repr, attributes(skip, pyo3_fmt_no_skip, format))]
+#[proc_macro_derive(Repr, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn repr_derive(input_stream: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input_stream as DeriveInput);
 
     // Get the name of the struct
     let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let display_debug_derive_body = impl_formatter(&input, DeriveType::ForAutoDebug, "Repr");
+    let display_debug_derive_body = impl_formatter(&input, DeriveType::ForAutoDebug, "Repr", true);
 
     let display_debug_derive_body = match display_debug_derive_body {
         Ok(x) => x,
-        Err(e) => e.into_compile_error(),
+        Err(e) => return e.into_compile_error().into(),
     };
 
+    let mut warnings = deprecation_warnings(&input);
+    warnings.extend(pyclass_order_warning(&input, "Repr"));
+
     let expanded = quote! {
         #display_debug_derive_body
+        #warnings
 
         #[pyo3::pymethods]
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
             pub fn __repr__(&self) -> String {
                 use pyo3_special_method_derive::PyDebug;
                 self.fmt_debug()
@@ -489,33 +369,33 @@ pub fn repr_derive(input_stream: TokenStream) -> TokenStream {
 ///
 /// This has the same requirements and behavior of [`Repr`].
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name is formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name is formatted.
 /// By default it is `{}.{}`. The format string takes 2 (filled in as name, variant), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3_special_method_derive::AutoDebug;
 /// #[derive(AutoDebug)]
-/// #[format(fmt = "{}.{}")]
+/// #[pyo3_smd(fmt = "{}.{}")]
 /// enum Person {
 ///     Alive,
 ///     Dead
 /// }
 /// ```
 ///
-/// The `formatter` attribute macro, when used to annotate an enum, controls how the type name and fields are formatted.
+/// The `fmt` attribute, when used to annotate an enum, controls how the type name and fields are formatted.
 /// By default it is `{}({})`. The format string takes 2 (filled in as name, fields), 1 (filled in as name), or 0 formatters:
 ///
 /// ```ignore
 /// use pyo3_special_method_derive::AutoDebug;
 /// #[derive(AutoDebug)]
-/// #[format(fmt = "{}({})")]
+/// #[pyo3_smd(fmt = "{}({})")]
 /// struct Mountain {
 ///     pub height: usize,
 /// }
 /// ```
 ///
-/// - A struct field may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
-/// - An enum variant may be annotated with `#[format(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
+/// - A struct field may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (field) or 0 formatters.
+/// - An enum variant may be annotated with `#[pyo3_smd(fmt = ...)]` where the format string can take 1 (variant) or 0 formatters.
 ///
 /// ## Example
 ///
@@ -532,29 +412,42 @@ pub fn repr_derive(input_stream: TokenStream) -> TokenStream {
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(AutoDebug, attributes(skip, pyo3_fmt_no_skip, format))]
+#[proc_macro_derive(AutoDebug, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn auto_debug(input_stream: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input_stream as DeriveInput);
     let name = &input.ident;
 
-    let display_debug_derive_body = impl_formatter(&input, DeriveType::ForAutoDebug, "AutoDebug");
+    let display_debug_derive_body =
+        impl_formatter(&input, DeriveType::ForAutoDebug, "AutoDebug", false);
 
     let display_debug_derive_body = match display_debug_derive_body {
         Ok(x) => x,
-        Err(e) => e.into_compile_error(),
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let warnings = deprecation_warnings(&input);
+
+    let display_debug_derive_body = quote! {
+        #display_debug_derive_body
+        #warnings
     };
 
     if implements_debug(name) {
         TokenStream::from(display_debug_derive_body)
     } else {
-        let name = &input.ident;
+        // Debug is available whenever PyDebug is
+        let mut generics = input.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(Self: pyo3_special_method_derive::PyDebug));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let expanded = quote! {
             #display_debug_derive_body
 
-            impl std::fmt::Debug for #name {
+            impl #impl_generics std::fmt::Debug for #name #ty_generics #where_clause {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     use pyo3_special_method_derive::PyDebug;
-                    write!(f, "{}", self.fmt_debug())
+                    self.fmt_debug_into(f)
                 }
             }
         };
@@ -565,462 +458,84 @@ pub fn auto_debug(input_stream: TokenStream) -> TokenStream {
 
 /// Add a `__getattr__` method to a struct or enum.
 ///
-/// - For structs, all fields are skipped which are not marked `pub`
-/// - Skip exposure of certain fields by adding `Getattr` to the `#[skip(...)]` attribute macro: `#[skip(Getattr)]`
-/// - Skip exposure of certain fields for all derive macros by adding `All`: `#[skip(All)]`
+/// - For structs, all fields are skipped which are not marked `pub` (restricted visibilities such as `pub(crate)` or `pub(super)` count as private)
+/// - Skip exposure of certain fields by adding `Getattr` to the `#[pyo3_smd(skip(...))]` attribute macro: `#[pyo3_smd(skip(Getattr))]`
+/// - Force exposure of a field which is not `pub` with `#[pyo3_smd(include)]`
+/// - Skip exposure of certain fields for all derive macros with `#[pyo3_smd(skip)]`
+/// - Expose a field under another name with `#[pyo3_smd(rename = "...")]` or `#[pyo3_smd(name = "...")]`
+/// - Fields of tuple structs and tuple variants are named `_0`, `_1`, ... like PyO3 does
+/// - Expose a field holding an `Arc<RwLock<T>>` or `Arc<Mutex<T>>` with `#[pyo3_smd(lock = "clone")]`, which converts a clone
+///   of the locked value, or with `#[pyo3_smd(lock = "shared")]` if the lock holds a `Py<T>`, which hands out that same object.
+///   A lock which is held elsewhere or poisoned raises a `RuntimeError`
 ///
 /// ## Example
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Getattr;
-/// #[pyclass]
 /// #[derive(Getattr)]
+/// #[pyclass]
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(Getattr, attributes(skip))]
+#[proc_macro_derive(Getattr, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn getattr_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = &input.ident;
-
-    let expanded = match input.data {
-        Data::Struct(data) => {
-            match data.fields {
-                Fields::Named(fields) => {
-                    // If the struct has named fields extract their names
-                    let field_names = fields
-                        .named
-                        .iter()
-                        .filter(|f| matches!(f.vis, Visibility::Public(_)))
-                        .filter(|f| {
-                            !f.attrs.iter().any(|attr| {
-                                let mut is_skip = false;
-                                if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                                    // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                                    attr.parse_nested_meta(|meta| {
-                                        is_skip |= meta.path.is_ident("Getattr")
-                                            || meta.path.is_ident(SKIP_ALL);
-                                        Ok(())
-                                    })
-                                    .unwrap();
-                                };
-                                is_skip
-                            })
-                        })
-                        .map(|f| f.ident.as_ref().unwrap())
-                        .collect::<Vec<_>>();
-                    let field_names_str = field_names
-                        .iter()
-                        .map(|f| f.to_string())
-                        .collect::<Vec<_>>();
-
-                    if field_names.is_empty() {
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                #[allow(non_snake_case)]
-                                pub fn __getattr__(&self, attr: String) -> pyo3::PyResult<pyo3::Py<pyo3::PyAny>> {
-                                    Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}' has no attribute '{attr}'", stringify!(#name))))
-                                }
-                            }
-                        }
-                    } else {
-                        // Prepare an array where the elements are expressions that prepare the field vec
-                        let mut matchers = Vec::new();
-                        for (name, ident) in field_names_str.iter().zip(field_names) {
-                            let inner = quote! {
-                                #name => {
-                                    Ok(pyo3::Python::with_gil(|py| self.#ident.clone().into_py(py)))
-                                }
-                            };
-                            matchers.push(inner);
-                        }
-
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                #[allow(non_snake_case)]
-                                pub fn __getattr__(&self, attr: String) -> pyo3::PyResult<pyo3::Py<pyo3::PyAny>> {
-                                    use pyo3::IntoPy;
-
-                                    match attr.as_str() {
-                                        #(#matchers)*
-                                        name => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}' has no attribute '{attr}'", stringify!(#name))))
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                Fields::Unit => {
-                    // If the struct has no fields
-                    quote! {
-                        #[pyo3::pymethods]
-                        impl #name {
-                            #[allow(non_snake_case)]
-                            pub fn __getattr__(&self, attr: String) -> pyo3::PyResult<pyo3::Py<pyo3::PyAny>> {
-                                Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}' has no attribute '{attr}'", #name)))
-                            }
-                        }
-                    }
-                }
-                Fields::Unnamed(_) => {
-                    quote! {
-                        compile_error!("Unnamed fields for struct are not supported for Getattr derive.");
-                    }
-                }
-            }
-        }
-        Data::Enum(data_enum) => {
-            let variants = data_enum.variants.iter().collect::<Vec<_>>();
-            let match_arms = variants.iter()
-            .filter(|variant| {
-                !variant.attrs.iter().any(|attr| {
-                    let mut is_skip = false;
-                    if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                        // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                        attr.parse_nested_meta(|meta| {
-                            is_skip |= meta.path.is_ident("Getattr") || meta.path.is_ident(SKIP_ALL);
-                            Ok(())
-                        })
-                        .unwrap();
-                    };
-                    is_skip
-                })
-            })
-                .map(|variant| {
-                let ident = &variant.ident;
-                match &variant.fields {
-                    Fields::Unit => {
-                        quote! {
-                            Self::#ident => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}.{}' has no attribute '{attr}'", stringify!(#name), stringify!(#ident)))),
-                        }
-                    }
-                    Fields::Unnamed(_) => {
-                        unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                    }
-                    Fields::Named(fields) => {
-                        let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap().clone()).collect::<Vec<_>>();
-                        let mut inserter = Vec::new();
-                        for ident_name in &field_names {
-                            inserter.push(
-                                quote! {
-                                    stringify!(#ident_name) => {
-                                        Ok(pyo3::Python::with_gil(|py| #ident_name.clone().into_py(py)))
-                                    }
-                                }
-                            );
-                        }
-                        inserter.push(
-                            quote! {
-                                _ => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}.{}' has no attribute '{attr}'", stringify!(#name), stringify!(#ident))))
-                            }
-                        );
-                        quote! {
-                            Self::#ident { #(#field_names),* } => {
-                                match attr.as_str() {
-                                    #(#inserter)*
-                                }
-                            }
-                        }
-                    }
-                }
-            }).collect::<Vec<_>>();
-            let ignored_match_arms = variants.iter()
-            .filter(|variant| {
-                variant.attrs.iter().any(|attr| {
-                    let mut is_skip = false;
-                    if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                        // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                        attr.parse_nested_meta(|meta| {
-                            is_skip |= meta.path.is_ident("Getattr") || meta.path.is_ident(SKIP_ALL);
-                            Ok(())
-                        })
-                        .unwrap();
-                    };
-                    is_skip
-                })
-            })
-                .map(|variant| {
-                let ident = &variant.ident;
-                // If a variant was ignored always raise an exception
-                match &variant.fields {
-                    Fields::Unit => {
-                        quote! {
-                            Self::#ident => Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}.{}' has no attribute '{attr}'", stringify!(#name), stringify!(#ident)))),
-                        }
-                    }
-                    Fields::Unnamed(_) => {
-                        unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                    }
-                    Fields::Named(fields) => {
-                        let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap().clone()).collect::<Vec<_>>();
-                        quote! {
-                            Self::#ident { #(#field_names),* } => {
-                                let _ = (#(#field_names),*);
-                                Err(pyo3::exceptions::PyAttributeError::new_err(format!("'{}.{}' has no attribute '{attr}'", stringify!(#name), stringify!(#ident))))
-                            }
-                        }
-                    }
-                }
-            }).collect::<Vec<_>>();
-            quote! {
-                #[pyo3::pymethods]
-                impl #name {
-                    #[allow(non_snake_case)]
-                    pub fn __getattr__(&self, attr: String) -> pyo3::PyResult<pyo3::Py<pyo3::PyAny>> {
-                        use pyo3::IntoPy;
-
-                        match self {
-                            #(#match_arms)*
-                            #(#ignored_match_arms)*
-                        }
-                    }
-                }
-            }
-        }
-        Data::Union(_) => {
-            quote! {
-                compile_error!("Unions are not supported for Getattr derive");
-            }
-        }
+    let expanded = match impl_getattr(&input) {
+        Ok(x) => x,
+        Err(e) => e.into_compile_error(),
     };
-    expanded.into()
+    let mut warnings = deprecation_warnings(&input);
+    warnings.extend(pyclass_order_warning(&input, "Getattr"));
+
+    TokenStream::from(quote! {
+        #expanded
+        #warnings
+    })
 }
 
 /// Add a `__dict__` attribute to a struct or enum.
 ///
-/// - For structs, all fields are skipped which are not marked `pub`
-/// - Skip exposure of certain fields by adding `Dict` to the `#[skip(...)]` attribute macro: `#[skip(Dict)]`
-/// - Skip exposure of certain fields for all derive macros by adding `All`: `#[skip(All)]`
+/// - For structs, all fields are skipped which are not marked `pub` (restricted visibilities such as `pub(crate)` or `pub(super)` count as private)
+/// - Skip exposure of certain fields by adding `Dict` to the `#[pyo3_smd(skip(...))]` attribute macro: `#[pyo3_smd(skip(Dict))]`
+/// - Force exposure of a field which is not `pub` with `#[pyo3_smd(include)]`
+/// - Skip exposure of certain fields for all derive macros with `#[pyo3_smd(skip)]`
+/// - Expose a field under another name with `#[pyo3_smd(rename = "...")]` or `#[pyo3_smd(name = "...")]`
+/// - Fields of tuple structs and tuple variants are named `_0`, `_1`, ... like PyO3 does
+/// - Expose a field holding an `Arc<RwLock<T>>` or `Arc<Mutex<T>>` with `#[pyo3_smd(lock = "clone")]`, which converts a clone
+///   of the locked value, or with `#[pyo3_smd(lock = "shared")]` if the lock holds a `Py<T>`, which hands out that same object.
+///   A lock which is held elsewhere or poisoned raises a `RuntimeError`
 ///
 /// ## Example
 /// ```ignore
 /// use pyo3::pyclass;
 /// use pyo3_special_method_derive::Dict;
-/// #[pyclass]
 /// #[derive(Dict)]
+/// #[pyclass]
 /// struct Person {
 ///     pub name: String,
 ///     address: String,
 ///     pub phone_number: String,
 /// }
 /// ```
-#[proc_macro_derive(Dict, attributes(skip))]
+#[proc_macro_derive(Dict, attributes(pyo3_smd, skip, pyo3_fmt_no_skip, format))]
 pub fn dict_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = &input.ident;
-
-    let expanded = match input.data {
-        Data::Struct(data) => {
-            match data.fields {
-                Fields::Named(fields) => {
-                    // If the struct has named fields extract their names
-                    let field_names = fields
-                        .named
-                        .iter()
-                        .filter(|f| {
-                            !f.attrs.iter().any(|attr| {
-                                let mut is_skip = false;
-                                if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                                    // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                                    attr.parse_nested_meta(|meta| {
-                                        is_skip |= meta.path.is_ident("Dict")
-                                            || meta.path.is_ident(SKIP_ALL);
-                                        Ok(())
-                                    })
-                                    .unwrap();
-                                };
-                                is_skip
-                            })
-                        })
-                        .filter(|f| matches!(f.vis, Visibility::Public(_)))
-                        .map(|f| f.ident.as_ref().unwrap())
-                        .collect::<Vec<_>>();
-
-                    if field_names.is_empty() {
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                #[allow(non_snake_case)]
-                                #[getter]
-                                pub fn __dict__(&self) -> std::collections::HashMap<String, pyo3::Py<pyo3::PyAny>> {
-                                    std::collections::HashMap::new()
-                                }
-                            }
-                        }
-                    } else {
-                        // Prepare an array where the elements are expressions that prepare the field vec
-                        let mut inserter = Vec::new();
-                        for name in field_names {
-                            inserter.push(
-                                quote! {
-                                    values.insert(
-                                            stringify!(#name).to_string(), pyo3::Python::with_gil(|py| self.#name.clone().into_py(py))
-                                    );
-                                }
-                            );
-                        }
-
-                        quote! {
-                            #[pyo3::pymethods]
-                            impl #name {
-                                #[allow(non_snake_case)]
-                                #[getter]
-                                pub fn __dict__(&self) -> std::collections::HashMap<String, pyo3::Py<pyo3::PyAny>> {
-                                    use pyo3::IntoPy;
-
-                                    let mut values = std::collections::HashMap::new();
-                                    #(#inserter)*
-                                    values
-                                }
-                            }
-                        }
-                    }
-                }
-                Fields::Unit => {
-                    // If the struct has no fields
-                    quote! {
-                        #[pyo3::pymethods]
-                        impl #name {
-                            #[allow(non_snake_case)]
-                            #[getter]
-                            pub fn __dict__(&self) -> std::collections::HashMap<String, pyo3::Py<pyo3::PyAny>> {
-                                std::collections::HashMap::new()
-                            }
-                        }
-                    }
-                }
-                Fields::Unnamed(_) => {
-                    quote! {
-                        compile_error!("Unnamed fields for struct are not supported for Dict derive.");
-                    }
-                }
-            }
-        }
-        Data::Enum(data_enum) => {
-            let variants = data_enum.variants.iter().collect::<Vec<_>>();
-            let match_arms = variants.iter()
-            .filter(|variant| {
-                !variant.attrs.iter().any(|attr| {
-                    let mut is_skip = false;
-                    if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                        // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                        attr.parse_nested_meta(|meta| {
-                            is_skip |= meta.path.is_ident("Dict") || meta.path.is_ident(SKIP_ALL);
-                            Ok(())
-                        })
-                        .unwrap();
-                    };
-                    is_skip
-                })
-            })
-                .map(|variant| {
-                let ident = &variant.ident;
-                match &variant.fields {
-                    Fields::Unit => {
-                        quote! {
-                            Self::#ident => { }
-                        }
-                    }
-                    Fields::Unnamed(_) => {
-                        unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                    }
-                    Fields::Named(fields) => {
-                        let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap().clone()).collect::<Vec<_>>();
-                        let mut inserter = Vec::new();
-                        for name in &field_names {
-                            inserter.push(
-                                quote! {
-                                    values.insert(
-                                            stringify!(#name).to_string(), pyo3::Python::with_gil(|py| #name.clone().into_py(py))
-                                    );
-                                }
-                            );
-                        }
-                        quote! {
-                            Self::#ident { #(#field_names),* } => {
-                                #(#inserter)*
-                            }
-                        }
-                    }
-                }
-            }).collect::<Vec<_>>();
-            let ignored_match_arms = variants
-                .iter()
-                .filter(|variant| {
-                    variant.attrs.iter().any(|attr| {
-                        let mut is_skip = false;
-                        if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                            // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                            attr.parse_nested_meta(|meta| {
-                                is_skip |=
-                                    meta.path.is_ident("Dict") || meta.path.is_ident(SKIP_ALL);
-                                Ok(())
-                            })
-                            .unwrap();
-                        };
-                        is_skip
-                    })
-                })
-                .map(|variant| {
-                    let ident = &variant.ident;
-                    // If a variant was ignored just output no __dict__ data.
-                    match &variant.fields {
-                        Fields::Unit => {
-                            quote! {
-                                Self::#ident => { }
-                            }
-                        }
-                        Fields::Unnamed(_) => {
-                            unreachable!("Unnamed fields are not supported for enums with PyO3.")
-                        }
-                        Fields::Named(fields) => {
-                            let field_names = fields
-                                .named
-                                .iter()
-                                .map(|f| f.ident.as_ref().unwrap().clone())
-                                .collect::<Vec<_>>();
-
-                            quote! {
-                                Self::#ident { #(#field_names),* } => {
-                                    let _ = (#(#field_names),*);
-                                }
-                            }
-                        }
-                    }
-                })
-                .collect::<Vec<_>>();
-            quote! {
-                #[pyo3::pymethods]
-                impl #name {
-                    #[allow(non_snake_case)]
-                    #[getter]
-                    pub fn __dict__(&self) -> std::collections::HashMap<String, pyo3::Py<pyo3::PyAny>> {
-                        use pyo3::IntoPy;
-
-                        let mut values = std::collections::HashMap::new();
-                        match self {
-                            #(#match_arms)*
-                            #(#ignored_match_arms)*
-                        }
-                        values
-                    }
-                }
-            }
-        }
-        Data::Union(_) => {
-            quote! {
-                compile_error!("Unions are not supported for Dict derive");
-            }
-        }
+    let expanded = match impl_dict(&input) {
+        Ok(x) => x,
+        Err(e) => e.into_compile_error(),
     };
-    expanded.into()
+    let mut warnings = deprecation_warnings(&input);
+    warnings.extend(pyclass_order_warning(&input, "Dict"));
+
+    TokenStream::from(quote! {
+        #expanded
+        #warnings
+    })
 }
 
 #[proc_macro_attribute]
@@ -1028,15 +543,35 @@ pub fn richcmp_derive_with(args: TokenStream, input: TokenStream) -> TokenStream
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
-    let args_parsed = syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated
-        .parse2(args.into())
-        .unwrap(); // Better to turn it into a `compile_error!()`
+    let args_parsed =
+        match syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated
+            .parse2(args.into())
+        {
+            Ok(x) => x,
+            Err(e) => return e.into_compile_error().into(),
+        };
 
     let mut do_partialeq = false;
     let mut do_partialord = false;
     for arg in args_parsed {
-        do_partialeq |= arg.is_ident("PartialEq");
-        do_partialord |= arg.is_ident("PartialOrd");
+        let seen = if arg.is_ident("PartialEq") {
+            &mut do_partialeq
+        } else if arg.is_ident("PartialOrd") {
+            &mut do_partialord
+        } else {
+            return syn::Error::new_spanned(
+                arg,
+                "unknown comparison trait, expected one of: `PartialEq`, `PartialOrd`",
+            )
+            .into_compile_error()
+            .into();
+        };
+        if *seen {
+            return syn::Error::new_spanned(arg, "duplicate comparison trait")
+                .into_compile_error()
+                .into();
+        }
+        *seen = true;
     }
 
     let partialeq_handler = if do_partialeq {
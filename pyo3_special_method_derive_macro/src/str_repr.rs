@@ -1,17 +1,16 @@
-use crate::{ATTR_NAMESPACE_FORMATTER, ATTR_NAMESPACE_NO_FMT_SKIP, ATTR_SKIP_NAMESPACE, SKIP_ALL};
-use proc_macro2::TokenStream;
+use crate::attrs::{validate_attrs, SmdAttrs};
+use crate::bounds::impl_generics;
+use crate::fields::{struct_fields, variant_fields, variant_pattern};
 use quote::quote;
-use syn::parse::{Parse, ParseStream};
-use syn::spanned::Spanned;
-use syn::{Attribute, DeriveInput, Fields, Ident, LitStr, Token, Visibility};
+use syn::{DeriveInput, Fields, LitStr, Type};
 macro_rules! create_body {
-    ($input:expr, $ident:expr, $is_repr:expr, $name:expr) => {
+    ($input:expr, $is_repr:expr, $container:expr, $type_name:expr, $macro_name:expr) => {
         match &$input.data {
             syn::Data::Struct(s) => {
-                generate_fmt_impl_for_struct(s, $ident, $is_repr, Some(&$input.attrs), $name)
+                generate_fmt_impl_for_struct(s, $is_repr, $container, $type_name, $macro_name)
             }
             syn::Data::Enum(e) => {
-                generate_fmt_impl_for_enum(e, $ident, $is_repr, Some(&$input.attrs), $name)
+                generate_fmt_impl_for_enum(e, $is_repr, $container, $type_name, $macro_name)
             }
             syn::Data::Union(u) => {
                 let error = syn::Error::new_spanned(u.union_token, "Unions are not supported");
@@ -35,400 +34,391 @@ pub(crate) fn impl_formatter(
     input: &DeriveInput,
     ty: DeriveType,
     name: &str,
+    pyclass: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
     // Get the name of the struct
     let ident = &input.ident;
     // Determine if the implementation is for a "repr" type
     let is_repr = matches!(ty, DeriveType::ForAutoDebug);
 
-    // Create body for display and debug
-    let body_display = create_body!(input, ident, is_repr, name)?;
-    let body_debug = create_body!(input, ident, is_repr, name)?;
+    validate_attrs(input)?;
+    let container = SmdAttrs::container(&input.attrs)?;
+    // The module is only shown by the unambiguous `Repr`/`AutoDebug` output
+    let type_name = container.type_name(ident, pyclass, is_repr);
+
+    let formatter = if is_repr {
+        quote! { fmt_debug_into }
+    } else {
+        quote! { fmt_display_into }
+    };
+    let (body, field_types) = if container.transparent {
+        generate_fmt_impl_for_transparent(input, &container, name, &formatter)?
+    } else {
+        create_body!(input, is_repr, &container, &type_name, name)?
+    };
+    // A transparent struct is displayed on its own just like its field, eg. without quotes
+    let str_method = if container.transparent && !is_repr {
+        let (str_body, _) =
+            generate_fmt_impl_for_transparent(input, &container, name, &quote! { fmt_str_into })?;
+        quote! {
+            fn fmt_str_into(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                pyo3_special_method_derive::guard_recursion(self, out, |out| {
+                    use pyo3_special_method_derive::PyDisplay;
+                    #str_body
+                })
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let trait_path = match ty {
+        DeriveType::ForAutoDisplay => quote! { pyo3_special_method_derive::PyDisplay },
+        DeriveType::ForAutoDebug => quote! { pyo3_special_method_derive::PyDebug },
+    };
+    let generics = impl_generics(&input.generics, &container, &field_types, Some(trait_path));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Determine which traits to implement
     match ty {
         DeriveType::ForAutoDisplay => Ok(quote! {
-            impl pyo3_special_method_derive::PyDisplay for #ident {
-                fn fmt_display(&self) -> String {
-                    use pyo3_special_method_derive::PyDisplay;
-                    #body_display
-                    repr
+            impl #impl_generics pyo3_special_method_derive::PyDisplay for #ident #ty_generics #where_clause {
+                fn fmt_display_into(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                    pyo3_special_method_derive::guard_recursion(self, out, |out| {
+                        use pyo3_special_method_derive::PyDisplay;
+                        #body
+                    })
                 }
+
+                #str_method
             }
         }),
         DeriveType::ForAutoDebug => Ok(quote! {
-            impl pyo3_special_method_derive::PyDebug for #ident {
-                fn fmt_debug(&self) -> String {
-                    use pyo3_special_method_derive::PyDebug;
-                    #body_debug
-                    repr
+            impl #impl_generics pyo3_special_method_derive::PyDebug for #ident #ty_generics #where_clause {
+                fn fmt_debug_into(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                    pyo3_special_method_derive::guard_recursion(self, out, |out| {
+                        use pyo3_special_method_derive::PyDebug;
+                        #body
+                    })
                 }
             }
         }),
     }
 }
 
-fn generate_fmt_impl_for_struct(
-    data_struct: &syn::DataStruct,
-    name: &Ident,
-    is_repr: bool,
-    string_formatter: Option<&Vec<Attribute>>,
-    macro_name: &str,
-) -> syn::Result<proc_macro2::TokenStream> {
-    let mut ident_formatter = quote! { #DEFAULT_STRUCT_IDENT_FORMATTER };
-    if let Some(attrs) = string_formatter {
-        for attr in attrs {
-            if attr.path().is_ident(ATTR_NAMESPACE_FORMATTER) {
-                if let Some(formatter) = find_display_attribute(attr) {
-                    ident_formatter = formatter;
-                    break;
+/// Number of formatters in a format string, counting every `{...}` placeholder but not the escaped
+/// `{{` and `}}`, or `None` if a brace is left unmatched.
+fn count_formatters(fmt: &str) -> Option<usize> {
+    let mut count = 0;
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => match chars.next()? {
+                '{' => {}
+                mut c => {
+                    while c != '}' {
+                        if c == '{' {
+                            return None;
+                        }
+                        c = chars.next()?;
+                    }
+                    count += 1;
                 }
-                break;
-            }
+            },
+            '}' if chars.next()? != '}' => return None,
+            _ => {}
         }
     }
+    Some(count)
+}
 
-    let fields = &data_struct.fields;
-    let fields = fields
-        .iter()
-        .filter(|f| {
-            // Default `is_skip` based on the field's visibility
-            let mut to_skip = !matches!(f.vis, Visibility::Public(_));
-
-            for attr in &f.attrs {
-                let path = attr.path();
-                if attr.path().is_ident(ATTR_SKIP_NAMESPACE) {
-                    // only parse ATTR_SKIP_NAMESPACE and not [serde] or [default]
-                    let _ = attr.parse_nested_meta(|meta| {
-                        to_skip |= meta.path.is_ident(macro_name) || meta.path.is_ident(SKIP_ALL);
-                        Ok(())
-                    });
-                    break;
-                } else if path.is_ident(ATTR_NAMESPACE_NO_FMT_SKIP) {
-                    // Explicitly mark to not skip the field
-                    to_skip = false;
-                    break;
-                }
-            }
-            !to_skip
-        })
-        .collect::<Vec<_>>();
-    let field_fmts = fields
-        .iter()
-        .enumerate()
-        .map(|(i, field)| {
-            let display_attr = {
-                let mut display_attr = None;
-
-                for attr in &field.attrs {
-                    let path = attr.path();
-                    if path.is_ident(ATTR_NAMESPACE_FORMATTER) {
-                        display_attr = Some(attr);
-                    }
-                }
-
-                display_attr
-            };
-
-            let mut variant_fmt = quote! { #DEFAULT_ELEMENT_FORMATTER };
-            if let Some(display_attr) = display_attr {
-                if let Some(formatter) = find_display_attribute(display_attr) {
-                    variant_fmt = formatter;
-                }
-            }
-
-            let formatters = variant_fmt.to_string().matches("{}").count()
-                - variant_fmt.to_string().matches("{{}}").count();
-            if formatters > 1 {
-                return Err(syn::Error::new(data_struct.struct_token.span(), "Specify 1 (variant), or 0 formatters in the format string."));
-            };
+/// The format string to use along with its number of formatters, which may not exceed `max_formatters`.
+fn parse_formatter(
+    fmt: &Option<LitStr>,
+    default: &str,
+    max_formatters: usize,
+    expected: &str,
+) -> syn::Result<(String, usize)> {
+    let Some(fmt) = fmt else {
+        let formatters = count_formatters(default).expect("the default formatters are valid");
+        return Ok((default.to_string(), formatters));
+    };
+    let value = fmt.value();
+    match count_formatters(&value) {
+        Some(formatters) if formatters <= max_formatters => Ok((value, formatters)),
+        Some(_) => Err(syn::Error::new_spanned(
+            fmt,
+            format!("Specify {expected} formatters in the format string."),
+        )),
+        None => Err(syn::Error::new_spanned(
+            fmt,
+            "Unmatched brace in the format string, escape it as `{{` or `}}`.",
+        )),
+    }
+}
 
-            let formatter_str = variant_fmt.to_string();
+/// Collect all of the `results`, combining their errors instead of stopping at the first one so
+/// that every invalid attribute is reported at once.
+fn collect_all<T>(results: impl IntoIterator<Item = syn::Result<T>>) -> syn::Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for result in results {
+        match (result, &mut errors) {
+            (Ok(value), _) => values.push(value),
+            (Err(e), Some(errors)) => errors.combine(e),
+            (Err(e), None) => errors = Some(e),
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(values),
+    }
+}
 
-            let format_str = format!("{{}}={}{{}}", &formatter_str[1..formatter_str.len()-1]);
+/// Both results, or the errors of either combined.
+fn join<A, B>(a: syn::Result<A>, b: syn::Result<B>) -> syn::Result<(A, B)> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok((a, b)),
+        (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+        (Err(mut a), Err(b)) => {
+            a.combine(b);
+            Err(a)
+        }
+    }
+}
 
-            let postfix = if i + 1 < fields.len() { ", " } else { "" };
-            let formatter = if is_repr { quote! { fmt_debug } } else { quote! { fmt_display } };
-            Ok(match &field.ident {
-                            Some(ident) => {
-                                if formatters > 0 {
-                                    quote! {
-                                        repr += &format!(#format_str, stringify!(#ident), self.#ident.#formatter(), #postfix);
-                                    }
-                                } else {
-                                    quote! {
-                                        repr += &format!(#format_str, stringify!(#ident), #postfix);
-                                    }
-                                }
-                            }
-                            None => {
-                                // If the field doesn't have a name, we generate a name based on its index
-                                let index = syn::Index::from(i);
-                                if formatters > 0 {
-                                    quote! {
-                                        repr += &format!(#format_str, stringify!(#index), self.#index.#formatter(), #postfix);
-                                    }
-                                } else {
-                                    quote! {
-                                        repr += &format!(#format_str, stringify!(#index), #postfix);
-                                    }
-                                }
-                            }
-                        })
-        })
-        .collect::<syn::Result<Vec<_>>>()?;
+/// Escape a name so that it can be embedded in a format string.
+fn escape_braces(name: &str) -> String {
+    name.replace('{', "{{").replace('}', "}}")
+}
 
-    // Handle any escaped {}
-    let formatters = ident_formatter.to_string().matches("{}").count()
-        - ident_formatter.to_string().matches("{{}}").count();
-    let ident_formatter = if formatters == 2 {
-        quote! { format!(#ident_formatter, stringify!(#name), repr) }
-    } else if formatters == 1 {
-        quote! { format!(#ident_formatter, stringify!(#name)) }
-    } else if formatters == 0 {
-        quote! { format!(#ident_formatter) }
-    } else {
-        return Err(syn::Error::new(
-            data_struct.struct_token.span(),
-            "Specify 2 (name, repr), 1 (name), or 0 formatters in the format string.",
+/// The body of the formatting method of a `transparent` struct, which calls `formatter` on its
+/// only field, along with the type of that field.
+fn generate_fmt_impl_for_transparent<'a>(
+    input: &'a DeriveInput,
+    container: &SmdAttrs,
+    macro_name: &str,
+    formatter: &proc_macro2::TokenStream,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<&'a Type>)> {
+    let syn::Data::Struct(data_struct) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`transparent` is only supported on structs",
+        ));
+    };
+    if let Some(fmt) = &container.fmt {
+        return Err(syn::Error::new_spanned(
+            fmt,
+            "`fmt` cannot be used on a `transparent` struct",
+        ));
+    }
+    let fields = struct_fields(data_struct, container, macro_name)?;
+    let [exposed] = fields.as_slice() else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "a `transparent` struct must display exactly one field",
         ));
     };
 
-    Ok(quote! {
-        let mut repr = "".to_string();
-        #(#field_fmts)*
-
-        let repr = #ident_formatter;
+    let (field_fmt, formatters) = parse_formatter(
+        &exposed.attrs.fmt,
+        DEFAULT_ELEMENT_FORMATTER,
+        1,
+        "1 (field), or 0",
+    )?;
+    let member = exposed.member();
+    Ok(if formatters > 0 {
+        let value = write_with(quote! { self.#member.#formatter(out) });
+        let body = quote! { write!(out, #field_fmt, #value) };
+        (body, vec![&exposed.field.ty])
+    } else {
+        (quote! { write!(out, #field_fmt) }, Vec::new())
     })
 }
 
-// Define a struct to hold the parsed tokens
-struct FmtAttribute {
-    ident: Ident,
-    _eq_token: Token![=],
-    pub lit_str: LitStr,
+/// An expression displayed by writing into `out` with `write`, to pass the output of a field to
+/// `write!` without going through a `String`.
+fn write_with(write: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! { pyo3_special_method_derive::display_with(|out| #write) }
 }
 
-// Implement parsing for the FmtAttribute struct
-impl Parse for FmtAttribute {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let ident: Ident = input.parse()?;
-        let _eq_token: Token![=] = input.parse()?;
-        let lit_str: LitStr = input.parse()?;
-        Ok(FmtAttribute {
-            ident,
-            _eq_token,
-            lit_str,
-        })
+/// Statements running each of the `writes` into `out`, separated by the configured separator.
+fn write_separated(writes: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    if writes.len() < 2 {
+        return quote! { #(#writes?;)* };
+    }
+    let (first, rest) = writes.split_first().expect("there are fields");
+    quote! {
+        let separator = &pyo3_special_method_derive::FormatConfig::current().separator;
+        #first?;
+        #(
+            out.write_str(separator)?;
+            #rest?;
+        )*
     }
 }
 
-pub fn find_display_attribute(attr: &Attribute) -> Option<TokenStream> {
-    // Parse the attribute arguments
-    let attribute = match attr.parse_args::<FmtAttribute>() {
-        Ok(display_macro) => Some(display_macro),
-        Err(e) => {
-            e.to_compile_error();
-            None
-        }
+/// The body of the formatting method, along with the types of the formatted fields.
+fn generate_fmt_impl_for_struct<'a>(
+    data_struct: &'a syn::DataStruct,
+    is_repr: bool,
+    container: &SmdAttrs,
+    type_name: &proc_macro2::TokenStream,
+    macro_name: &str,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<&'a Type>)> {
+    let ident_formatter = parse_formatter(
+        &container.fmt,
+        DEFAULT_STRUCT_IDENT_FORMATTER,
+        2,
+        "2 (name, fields), 1 (name), or 0",
+    );
+
+    let fields = struct_fields(data_struct, container, macro_name)?;
+
+    let formatter = if is_repr {
+        quote! { fmt_debug_into }
+    } else {
+        quote! { fmt_display_into }
     };
-
-    // Check if we have a valid attribute and return the literal as TokenStream
-    if let Some(attr) = attribute {
-        if attr.ident == "fmt" {
-            let list_str = attr.lit_str;
-            Some(quote! { #list_str })
+    let mut field_types = Vec::new();
+    let field_writes = collect_all(fields.iter().map(|exposed| {
+        let (field_fmt, formatters) = parse_formatter(
+            &exposed.attrs.fmt,
+            DEFAULT_ELEMENT_FORMATTER,
+            1,
+            "1 (field), or 0",
+        )?;
+
+        // Named fields are shown as `name=value`, tuple fields only by their value, just like
+        // tuple variants
+        let field_fmt = match &exposed.field.ident {
+            Some(_) => format!("{}={field_fmt}", escape_braces(&exposed.name())),
+            None => field_fmt,
+        };
+        Ok(if formatters > 0 {
+            field_types.push(&exposed.field.ty);
+            let member = exposed.member();
+            let value = write_with(quote! { self.#member.#formatter(out) });
+            quote! { write!(out, #field_fmt, #value) }
         } else {
-            None
+            quote! { write!(out, #field_fmt) }
+        })
+    }));
+    let ((ident_formatter, ident_formatters), field_writes) = join(ident_formatter, field_writes)?;
+
+    let body = match ident_formatters {
+        2 => {
+            let writes = write_separated(&field_writes);
+            let fields = write_with(quote! {{
+                #writes
+                Ok(())
+            }});
+            quote! { write!(out, #ident_formatter, #type_name, #fields) }
         }
-    } else {
-        None
-    }
+        1 => quote! { write!(out, #ident_formatter, #type_name) },
+        _ => quote! { write!(out, #ident_formatter) },
+    };
+    Ok((body, field_types))
 }
 
-fn generate_fmt_impl_for_enum(
-    data_enum: &syn::DataEnum,
-    name: &Ident,
+/// The body of the formatting method, along with the types of the formatted fields.
+fn generate_fmt_impl_for_enum<'a>(
+    data_enum: &'a syn::DataEnum,
     is_repr: bool,
-    string_formatter: Option<&Vec<Attribute>>,
+    container: &SmdAttrs,
+    type_name: &proc_macro2::TokenStream,
     macro_name: &str,
-) -> syn::Result<proc_macro2::TokenStream> {
-    let variants = data_enum.variants.iter().collect::<Vec<_>>();
-    let mut ident_formatter = quote! { #DEFAULT_ENUM_IDENT_FORMATTER };
-    if let Some(attrs) = string_formatter {
-        for attr in attrs {
-            if attr.path().is_ident(ATTR_NAMESPACE_FORMATTER) {
-                if let Some(formatter) = find_display_attribute(attr) {
-                    ident_formatter = formatter;
-                    break;
-                }
-                break;
-            }
-        }
-    }
-
-    let arms = variants.iter().map(|variant| {
-        let ident = &variant.ident;
-        let (to_skip, display_attr) = {
-            let mut to_skip = false;
-            let mut display_attr = None;
-
-            for attr in &variant.attrs {
-                let path = attr.path();
-                if path.is_ident(ATTR_SKIP_NAMESPACE)  {
-                    let _ = attr.parse_nested_meta(|meta| {
-                        to_skip |= meta.path.is_ident(macro_name) || meta.path.is_ident(SKIP_ALL);
-                        Ok(())
-                    });
-                    if path.is_ident(ATTR_NAMESPACE_FORMATTER) {
-                        display_attr = Some(attr);
-                    }
-                }
-            }
-
-            (to_skip, display_attr)
-        };
+) -> syn::Result<(proc_macro2::TokenStream, Vec<&'a Type>)> {
+    let ident_formatter = parse_formatter(
+        &container.fmt,
+        DEFAULT_ENUM_IDENT_FORMATTER,
+        2,
+        "2 (name, variant), 1 (name), or 0",
+    );
+    let formatter = if is_repr {
+        quote! { fmt_debug_into }
+    } else {
+        quote! { fmt_display_into }
+    };
 
-        let mut variant_fmt = quote! { #DEFAULT_ELEMENT_FORMATTER };
-        if let Some(display_attr) = display_attr {
-            if let Some(formatter) = find_display_attribute(display_attr) {
-                variant_fmt = formatter;
-            }
+    let mut field_types = Vec::new();
+    let arms = collect_all(data_enum.variants.iter().map(|variant| {
+        let variant_attrs = SmdAttrs::variant(&variant.attrs)?;
+        let variant_name = variant_attrs.name_or(&variant.ident);
+        if variant_attrs.is_skipped(macro_name) {
+            let pattern = variant_pattern(variant, &[]);
+            return Ok(quote! { #pattern => out.write_str("<variant skipped>"), });
         }
 
-        // If {} is not in ident_fmt, we must not format ident.
-        // If {} is not in variant_fmt, we don't use stringify! either
-        match &variant.fields {
-            Fields::Unit => {
-                let formatters = variant_fmt.to_string().matches("{}").count()
-                    - variant_fmt.to_string().matches("{{}}").count();
-                let variant_formatter = if formatters == 1 {
-                    quote! { &format!(#variant_fmt, stringify!(#ident)) }
-                } else if formatters == 0 {
-                    quote! { &format!(#variant_fmt) }
-                } else {
-                    return Err(syn::Error::new(data_enum.enum_token.span(), "Specify 1 (variant), or 0 formatters in the format string."));
+        let variant_fmt = parse_formatter(
+            &variant_attrs.fmt,
+            DEFAULT_ELEMENT_FORMATTER,
+            1,
+            "1 (variant), or 0",
+        );
+        let mut bound = Vec::new();
+        let field_writes = collect_all(variant_fields(variant, macro_name)?.into_iter().map(
+            |exposed| {
+                let (field_fmt, formatters) = parse_formatter(
+                    &exposed.attrs.fmt,
+                    DEFAULT_ELEMENT_FORMATTER,
+                    1,
+                    "1 (field), or 0",
+                )?;
+
+                // Named fields are shown as `name=value`, tuple fields only by their value
+                let field_fmt = match &exposed.field.ident {
+                    Some(_) => format!("{}={field_fmt}", escape_braces(&exposed.name())),
+                    None => field_fmt,
                 };
-
-                Ok(if !to_skip {
-                    quote! {
-                        Self::#ident => repr += #variant_formatter,
-                    }
+                Ok(if formatters > 0 {
+                    let binding = exposed.binding();
+                    field_types.push(&exposed.field.ty);
+                    bound.push(exposed.index);
+                    let value = write_with(quote! { #binding.#formatter(out) });
+                    quote! { write!(out, #field_fmt, #value) }
                 } else {
-                    quote! {
-                        Self::#ident => repr += "<variant skipped>",
-                    }
+                    quote! { write!(out, #field_fmt) }
                 })
-            }
-            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-                // Tuple variant with one field
-                // TODO now that we have AutoDisplay we want this
-                Ok(if !to_skip {
-                    quote! { #name::#ident(ref single) => {#ident_formatter;} }
-                } else {
-                    quote! {
-                        #ident => repr += "<variant skipped>",
-                    }
-                })
-            }
-            Fields::Named(fields) => {
-                let mut field_names: Vec<(&Option<Ident>, String, usize)> = Vec::new();
-                for field in &fields.named {
-                    let display_attr = {
-                        let mut display_attr = None;
-
-                        for attr in &field.attrs {
-                            let path = attr.path();
-                            if path.is_ident(ATTR_NAMESPACE_FORMATTER) {
-                                display_attr = Some(attr);
-                            }
-                        }
-
-                        display_attr
-                    };
-
-                    let mut variant_fmt = quote! { #DEFAULT_ELEMENT_FORMATTER };
-                    if let Some(display_attr) = display_attr {
-                        if let Some(formatter) = find_display_attribute(display_attr) {
-                            variant_fmt = formatter;
-                        }
-                    }
-
-                    let formatters = variant_fmt.to_string().matches("{}").count()
-                        - variant_fmt.to_string().matches("{{}}").count();
-                    if formatters > 1 {
-                        return Err(syn::Error::new(data_enum.enum_token.span(), "Specify 1 (variant), or 0 formatters in the format string."));
-                    };
-                    let formatter_str = variant_fmt.to_string();
+            },
+        ));
+        let ((variant_fmt, variant_formatters), field_writes) = join(variant_fmt, field_writes)?;
 
-                    field_names.push((&field.ident, formatter_str[1..formatter_str.len()-1].to_string(), formatters));
-                }
+        // If {} is not in variant_fmt, we don't format the variant name either
+        let variant_write = if variant_formatters > 0 {
+            quote! { write!(out, #variant_fmt, #variant_name) }
+        } else {
+            quote! { write!(out, #variant_fmt) }
+        };
+        if matches!(variant.fields, Fields::Unit) {
+            let pattern = variant_pattern(variant, &[]);
+            return Ok(quote! { #pattern => #variant_write, });
+        }
 
-                let mut format_string = "{}(".to_string();
-                let formatter = if is_repr { quote! { fmt_debug } } else { quote! { fmt_display } };
-                for (i, (name, formatter, _n_formatters)) in field_names.iter().enumerate() {
-                    if i == 0 {
-                        format_string = format!("{format_string}{}={}", name.as_ref().unwrap(), formatter);
-                    } else {
-                        format_string = format!("{format_string}, {}={}", name.as_ref().unwrap(), formatter);
-                    }
-                }
-                format_string = format!("{format_string})");
-                Ok(if !to_skip {
-                    let mut names = Vec::new();
-                    for (name, _, n_formatters) in field_names.clone() {
-                        if n_formatters > 0 {
-                            names.push(quote! { #name.#formatter() });
-                        }
-                    }
-                    let mut new_field_names = Vec::new();
-                    for (name, _, _) in field_names.clone() {
-                        new_field_names.push(name);
-                    }
-                    quote! {
-                        Self::#ident { #(#new_field_names),* } => repr += &format!(#format_string, stringify!(#ident), #(#names),*),
-                    }
-                } else {
-                    let mut names = Vec::new();
-                    for (name, _, _) in field_names.clone() {
-                        names.push(quote! { #name });
-                    }
-                    quote! {
-                        Self::#ident { #(#names),* } => {
-                            let _ = (#(#names),*);
-                            repr += "<variant skipped>";
-                        }
-                    }
-                })
-            }
-            _ => {
-                // Default case: stringify the variant name
-                Ok(quote! {  &format!("{}", stringify!(#ident)); })
+        let pattern = variant_pattern(variant, &bound);
+        let writes = write_separated(&field_writes);
+        Ok(quote! {
+            #pattern => {
+                #variant_write?;
+                out.write_str("(")?;
+                #writes
+                out.write_str(")")
             }
+        })
+    }));
+    let ((ident_formatter, ident_formatters), arms) = join(ident_formatter, arms)?;
+
+    let body = match ident_formatters {
+        2 => {
+            let variant = write_with(quote! {
+                match self {
+                    #(#arms)*
+                }
+            });
+            quote! { write!(out, #ident_formatter, #type_name, #variant) }
         }
-    }).collect::<syn::Result<Vec<_>>>()?;
-
-    // Handle any escaped {}
-    let formatters = ident_formatter.to_string().matches("{}").count()
-        - ident_formatter.to_string().matches("{{}}").count();
-    let ident_formatter = if formatters == 2 {
-        quote! { format!(#ident_formatter, stringify!(#name), repr) }
-    } else if formatters == 1 {
-        quote! { format!(#ident_formatter, stringify!(#name)) }
-    } else if formatters == 0 {
-        quote! { format!(#ident_formatter) }
-    } else {
-        return Err(syn::Error::new(
-            data_enum.enum_token.span(),
-            "Specify 2 (name, repr), 1 (name), or 0 formatters in the format string.",
-        ));
+        1 => quote! { write!(out, #ident_formatter, #type_name) },
+        _ => quote! { write!(out, #ident_formatter) },
     };
-
-    Ok(quote! {
-        let mut repr = "".to_string();
-        match self {
-            #(#arms)*
-        }
-        let repr = #ident_formatter;
-    })
+    Ok((body, field_types))
 }